@@ -0,0 +1,105 @@
+//! Compares translations opening a new connection for each request with translations sharing a
+//! pooled [`HttpClient`], against a local HTTPS stub.
+//!
+//! Run with `cargo bench --bench client`.
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use hyper::{server::conn::Http, service::service_fn, Body, Response};
+use text_translator::*;
+use tokio::{net::TcpListener, runtime::Runtime};
+use tokio_rustls::{
+    rustls::{Certificate, NoClientAuth, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+
+const TRANSLATE_RESPONSE: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+/// Starts an HTTPS server answering every request with a Lingva translation.
+///
+/// Returns its address and the PEM certificate to trust.
+fn start_stub(rt: &mut Runtime) -> (SocketAddr, String) {
+    let certified = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+        .expect("generate certificate");
+    let cert = certified.cert.pem();
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(
+            vec![Certificate(certified.cert.der().to_vec())],
+            PrivateKey(certified.key_pair.serialize_der()),
+        )
+        .expect("load certificate");
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+
+    let mut listener = rt
+        .block_on(TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))))
+        .expect("bind stub");
+    let addr = listener.local_addr().expect("stub address");
+
+    rt.spawn(async move {
+        loop {
+            let (stream, _) = match listener.accept().await {
+                Ok(res) => res,
+                Err(_) => continue,
+            };
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                if let Ok(stream) = acceptor.accept(stream).await {
+                    let service = service_fn(|_| async {
+                        Ok::<_, Infallible>(Response::new(Body::from(TRANSLATE_RESPONSE)))
+                    });
+                    let _ = Http::new().serve_connection(stream, service).await;
+                }
+            });
+        }
+    });
+
+    (addr, cert)
+}
+
+fn translations(c: &mut Criterion) {
+    let mut rt = Runtime::new().expect("start runtime");
+    let (addr, cert) = start_stub(&mut rt);
+    let base_url = format!("https://localhost:{}", addr.port());
+
+    let transport =
+        || HyperTransport::with_root_certificates(cert.as_bytes()).expect("trust certificate");
+
+    let mut group = c.benchmark_group("translate over HTTPS");
+
+    // a new client, and so a new connection and TLS handshake, for each request
+    group.bench_function("new connection per request", |b| {
+        b.iter(|| {
+            let client = HttpClient::with_transport(transport());
+            let translator = Lingva::new().with_base_url(&base_url).with_client(&client);
+            rt.block_on(translator.translate(
+                "Hello".to_string(),
+                InputLanguage::Automatic,
+                Language::French,
+            ))
+            .expect("translate")
+        })
+    });
+
+    // a long-lived client, reusing its pooled connection
+    let client = HttpClient::with_transport(transport());
+    let translator = Lingva::new().with_base_url(&base_url).with_client(&client);
+    group.bench_function("pooled client", |b| {
+        b.iter(|| {
+            rt.block_on(translator.translate(
+                "Hello".to_string(),
+                InputLanguage::Automatic,
+                Language::French,
+            ))
+            .expect("translate")
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, translations);
+criterion_main!(benches);
@@ -0,0 +1,16 @@
+// Checks the TLS features before the dependencies are compiled: with both of them, hyper-proxy fails to build
+// first, with errors unrelated to the actual mistake.
+use std::env;
+
+fn main() {
+    let native_tls = env::var_os("CARGO_FEATURE_NATIVE_TLS").is_some();
+    let rustls = env::var_os("CARGO_FEATURE_RUSTLS").is_some();
+
+    let error = match (native_tls, rustls) {
+        (true, true) => "the `native-tls` and `rustls` features are mutually exclusive: disable the default features to use `rustls`",
+        (false, false) => "a TLS backend is required: enable either the `native-tls` or the `rustls` feature",
+        _ => return,
+    };
+    eprintln!("error: {}", error);
+    std::process::exit(1);
+}
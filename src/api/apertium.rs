@@ -0,0 +1,457 @@
+/*!
+A module containing the implementation of the [Apertium APy API](https://wiki.apertium.org/wiki/Apertium-apy).
+
+To use it, see the [`Apertium struct`](struct.Apertium.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Default URL of a local APy server.
+pub const APERTIUM_BASE_URL: &str = "http://localhost:2737";
+
+/// Explanation given by APy when a pair of languages is not installed.
+const PAIR_NOT_INSTALLED: &str = "That pair is not installed";
+
+/// # Apertium API
+///
+/// A struct representing an [APy](https://wiki.apertium.org/wiki/Apertium-apy) server, the HTTP API of the
+/// [Apertium](https://www.apertium.org) rule-based translation platform.
+///
+/// Apertium works best between closely related languages, e.g. Spanish and Catalan.
+/// Only the pairs installed on the server can be used, see [`installed_pairs`](struct.Apertium.html#method.installed_pairs).
+///
+/// Languages are sent as ISO 639-3 codes, e.g. `spa` for Spanish, and Norwegian as Bokmål (`nob`).
+/// Apertium does not detect the source language, so it must be defined.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from Spanish to Catalan, with a local server:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct, using a server on the default port
+/// let translator = Apertium::new();
+///
+/// let text: String = "Hola, me llamo Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Defined(Language::Spanish), Language::Catalan)
+///     .await?;
+///
+/// assert_eq!(translated_text, "Hola, em dic Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Installed pairs
+///
+/// List the pairs of languages installed on a server:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = Apertium::new().with_base_url("https://www.apertium.org/apy");
+///
+/// let pairs = translator.installed_pairs().await?;
+///
+/// assert!(pairs.contains(&(Language::Spanish, Language::Catalan)));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Apertium<'a> {
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> Apertium<'a> {
+    /// Sends the requests to the server at the given URL instead of [`APERTIUM_BASE_URL`](constant.APERTIUM_BASE_URL.html).
+    ///
+    /// The URL must not end with a slash, e.g. `https://www.apertium.org/apy`.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the `(source, target)` pairs of languages installed on the server.
+    ///
+    /// Pairs using a language variant (e.g. `cat_valencia`) or a language missing from
+    /// [`Language`](../enum.Language.html) are not returned.
+    pub async fn installed_pairs(&self) -> Result<Vec<(Language, Language)>, Error> {
+        let url = format!("{}/listPairs", self.base_url);
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri).await?;
+
+        let json_body: ListPairsResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body
+            .response_data
+            .iter()
+            .filter_map(|pair| {
+                Some((
+                    language_from_code(&pair.source_language)?,
+                    language_from_code(&pair.target_language)?,
+                ))
+            })
+            .collect())
+    }
+}
+
+#[async_trait]
+impl<'a> Api for Apertium<'a> {
+    /// Returns a new [`Apertium`](struct.Apertium.html) struct, using a local server on the default port.
+    fn new() -> Self {
+        Self {
+            base_url: APERTIUM_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => {
+                return Err(Error::ApertiumAPIError(
+                    ApertiumError::SourceLanguageRequired.into(),
+                ))
+            }
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                source
+            }
+        };
+
+        // build query
+        let url = format!(
+            "{}/translate?langpair={}%7C{}&q={}",
+            self.base_url,
+            language_code(source_language),
+            language_code(target_language),
+            encode(&text)
+        );
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+/// Returns the ISO 639-3 code of a language, used by Apertium.
+fn language_code(language: Language) -> &'static str {
+    use Language::*;
+    match language {
+        Afrikaans => "afr",
+        Albanian => "sqi",
+        Amharic => "amh",
+        Arabic => "ara",
+        Armenian => "hye",
+        Azerbaijan => "aze",
+        Bashkir => "bak",
+        Basque => "eus",
+        Belarusian => "bel",
+        Bengali => "ben",
+        Bosnian => "bos",
+        Bulgarian => "bul",
+        Burmese => "mya",
+        Catalan => "cat",
+        Cebuano => "ceb",
+        Chinese => "zho",
+        Croatian => "hrv",
+        Czech => "ces",
+        Danish => "dan",
+        Dutch => "nld",
+        English => "eng",
+        Esperanto => "epo",
+        Estonian => "est",
+        Finnish => "fin",
+        French => "fra",
+        Galician => "glg",
+        Georgian => "kat",
+        German => "deu",
+        Greek => "ell",
+        Gujarati => "guj",
+        HaitianCreole => "hat",
+        Hebrew => "heb",
+        HillMari => "mrj",
+        Hindi => "hin",
+        Hungarian => "hun",
+        Icelandic => "isl",
+        Indonesian => "ind",
+        Irish => "gle",
+        Italian => "ita",
+        Japanese => "jpn",
+        Javanese => "jav",
+        Kannada => "kan",
+        Kazakh => "kaz",
+        Khmer => "khm",
+        Korean => "kor",
+        Kyrgyz => "kir",
+        Laotian => "lao",
+        Latin => "lat",
+        Latvian => "lav",
+        Lithuanian => "lit",
+        Luxembourgish => "ltz",
+        Macedonian => "mkd",
+        Malagasy => "mlg",
+        Malay => "msa",
+        Malayalam => "mal",
+        Maltese => "mlt",
+        Maori => "mri",
+        Marathi => "mar",
+        Mari => "mhr",
+        Mongolian => "mon",
+        Nepali => "nep",
+        Norwegian => "nob",
+        Papiamento => "pap",
+        Persian => "fas",
+        Polish => "pol",
+        Portuguese => "por",
+        Punjabi => "pan",
+        Romanian => "ron",
+        Russian => "rus",
+        Scottish => "gla",
+        Serbian => "srp",
+        Sinhala => "sin",
+        Slovakian => "slk",
+        Slovenian => "slv",
+        Spanish => "spa",
+        Sundanese => "sun",
+        Swahili => "swa",
+        Swedish => "swe",
+        Tagalog => "tgl",
+        Tajik => "tgk",
+        Tamil => "tam",
+        Tatar => "tat",
+        Telugu => "tel",
+        Thai => "tha",
+        Turkish => "tur",
+        Udmurt => "udm",
+        Ukrainian => "ukr",
+        Urdu => "urd",
+        Uzbek => "uzb",
+        Vietnamese => "vie",
+        Welsh => "cym",
+        Xhosa => "xho",
+        Yiddish => "yid",
+    }
+}
+
+/// Returns the language of an ISO 639-3 code, as used by Apertium.
+fn language_from_code(code: &str) -> Option<Language> {
+    Language::iterator()
+        .find(|language| language_code(**language) == code)
+        .copied()
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(client: Option<&HttpClient>, uri: Uri) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::ApertiumAPIError(ApertiumError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of an APy translate response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    response_data: TranslateData,
+}
+
+/// Translated text in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateData {
+    translated_text: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.response_data.translated_text.clone()
+    }
+}
+
+/// Serializable struct of an APy listPairs response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListPairsResponse {
+    response_data: Vec<Pair>,
+}
+
+/// Installed pair in a ListPairsResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Pair {
+    source_language: String,
+    target_language: String,
+}
+
+/// Serializable struct of an APy error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    #[serde(default)]
+    explanation: String,
+}
+
+/// Enum containing different errors that may be returned by an APy server.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ApertiumError {
+    /// The pair of languages is not installed on the server.
+    PairNotInstalled,
+    /// Apertium cannot detect the source language, it must be defined.
+    SourceLanguageRequired,
+    /// Invalid request, e.g. a missing parameter.
+    BadRequest,
+    /// The API is not available at this URL.
+    NotFound,
+    /// The server failed to translate the text.
+    InternalError,
+    /// The server is overloaded.
+    ServiceUnavailable,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for ApertiumError {
+    fn from_error_code(code: u16) -> Self {
+        use ApertiumError::*;
+        match code {
+            400 => BadRequest,
+            404 => NotFound,
+            500 => InternalError,
+            503 => ServiceUnavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use ApertiumError::*;
+        match self {
+            PairNotInstalled | SourceLanguageRequired | BadRequest => 400,
+            NotFound => 404,
+            InternalError => 500,
+            ServiceUnavailable => 503,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `explanation` of the error response, kept as the message.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) if !res.explanation.is_empty() => {
+                let kind = if res.explanation == PAIR_NOT_INSTALLED {
+                    ApertiumError::PairNotInstalled
+                } else {
+                    Self::from_error_code(status)
+                };
+                ProviderError::new(kind, status).with_message(&res.explanation)
+            }
+            _ => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use ApertiumError::*;
+        match self {
+            InternalError | ServiceUnavailable => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use ApertiumError::*;
+        match self {
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use ApertiumError::*;
+        match self {
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use ApertiumError::*;
+        matches!(self, PairNotInstalled | SourceLanguageRequired)
+    }
+}
+
+impl std::fmt::Display for ApertiumError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Apertium error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for ApertiumError {}
@@ -0,0 +1,476 @@
+/*!
+A module containing the implementation of the [Microsoft Translator API](https://docs.microsoft.com/en-us/azure/cognitive-services/translator/reference/v3-0-reference), part of Azure Cognitive Services.
+
+To use it, see the [`AzureTranslator struct`](struct.AzureTranslator.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+use super::*;
+
+/// Base URL used to access the global Microsoft Translator API.
+pub const AZURE_BASE_URL: &str = "https://api.cognitive.microsofttranslator.com";
+
+/// Version of the API used by the requests.
+const API_VERSION: &str = "3.0";
+
+/// Helper structure of an element of the request body of an Azure request
+#[derive(Serialize)]
+struct AzureRequestItem<'a> {
+    #[serde(rename = "Text")]
+    text: &'a str,
+}
+
+/// # Microsoft Translator API
+///
+/// A struct representing the [Microsoft Translator API](https://docs.microsoft.com/en-us/azure/cognitive-services/translator/),
+/// previously known as Bing Translator.
+///
+/// This API needs a subscription key, which can be created by adding a Translator resource in the
+/// [Azure portal](https://portal.azure.com). If the resource is not global, its region must be
+/// set with [`with_region`](struct.AzureTranslator.html#method.with_region).
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+/// - API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+///
+/// Translating a text to several languages in a single request is possible with
+/// [`translate_to_many`](struct.AzureTranslator.html#method.translate_to_many).
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to German and Japanese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = AzureTranslator::with_key("<AZURE_SUBSCRIPTION_KEY>").with_region("westeurope");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<Vec<String>, Error>`
+/// let translations: Vec<String> = translator
+///     .translate_to_many(text, InputLanguage::Automatic, &[Language::German, Language::Japanese])
+///     .await?;
+///
+/// assert_eq!(translations[0], "Hallo, mein Name ist Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = AzureTranslator::with_key("<AZURE_SUBSCRIPTION_KEY>");
+/// let text: String = "Bonjour, je m'appelle Naruto Uzumaki!".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::French));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct AzureTranslator<'a> {
+    key: Option<&'a str>,
+    region: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> AzureTranslator<'a> {
+    /// Returns a new [`AzureTranslator`](struct.AzureTranslator.html) struct with the given subscription key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            key: Some(key),
+            region: None,
+            base_url: AZURE_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sets the region of the Translator resource, sent in the `Ocp-Apim-Subscription-Region` header.
+    ///
+    /// It is not needed for global resources.
+    pub const fn with_region(mut self, region: &'a str) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Sends the requests to the given base URL instead of [`AZURE_BASE_URL`](constant.AZURE_BASE_URL.html),
+    /// e.g. a regional endpoint such as `https://api-eur.cognitive.microsofttranslator.com`.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the region of the Translator resource, if set.
+    pub fn get_region(&self) -> Option<&'a str> {
+        self.region
+    }
+
+    /// Translates text to several languages in a single request.
+    ///
+    /// Returns the translations in the same order as `target_languages`.
+    pub async fn translate_to_many(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_languages: &[Language],
+    ) -> Result<Vec<String>, Error> {
+        // get translation direction
+        let mut query = format!("{}/translate?api-version={}", self.base_url, API_VERSION);
+        if let InputLanguage::Defined(source) = source_language {
+            // verify that source languages != target languages
+            if let Some(target) = target_languages.iter().find(|&&target| target == source) {
+                return Err(Error::SameLanguages(source, *target));
+            }
+
+            query = format!("{}&from={}", query, language_code(source));
+        }
+        for target in target_languages {
+            query = format!("{}&to={}", query, language_code(*target));
+        }
+
+        let body = get_response(self, query, &text).await?;
+
+        let json_body: Vec<TranslateResponse> = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        let translations = json_body
+            .into_iter()
+            .next()
+            .ok_or(Error::CouldNotDerializeJson)?
+            .translations;
+        if translations.len() != target_languages.len() {
+            return Err(Error::CouldNotDerializeJson);
+        }
+
+        Ok(translations
+            .into_iter()
+            .map(|translation| translation.text)
+            .collect())
+    }
+}
+
+impl<'a> ApiKey<'a> for AzureTranslator<'a> {
+    fn set_set(&mut self, key: &'a str) {
+        self.key = Some(key)
+    }
+
+    fn get_key(&self) -> Option<&'a str> {
+        self.key
+    }
+}
+
+#[async_trait]
+impl<'a> Api for AzureTranslator<'a> {
+    /// Returns a new [`AzureTranslator`](struct.AzureTranslator.html) struct without subscription key.
+    ///
+    /// To set it, use [`with_key`](struct.AzureTranslator.html#method.with_key) or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
+    fn new() -> Self {
+        Self {
+            key: None,
+            region: None,
+            base_url: AZURE_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        self.translate_to_many(text, source_language, &[target_language])
+            .await?
+            .pop()
+            .ok_or(Error::CouldNotDerializeJson)
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for AzureTranslator<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        // build query
+        let query = format!("{}/detect?api-version={}", self.base_url, API_VERSION);
+
+        let body = get_response(self, query, &text).await?;
+
+        let json_body: Vec<DetectResponse> = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.first().and_then(|detection| detection.get_lang()))
+    }
+}
+
+/// Returns the code used by Azure for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-Hans",
+        Language::Norwegian => "nb",
+        Language::Serbian => "sr-Cyrl",
+        Language::Tagalog => "fil",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the language matching a code returned by Azure.
+fn language_from_code(code: &str) -> Option<Language> {
+    match code {
+        "zh-Hans" | "zh-Hant" => Some(Language::Chinese),
+        "nb" => Some(Language::Norwegian),
+        "sr-Cyrl" | "sr-Latn" => Some(Language::Serbian),
+        "fil" => Some(Language::Tagalog),
+        other => Language::from_language_code(other),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    translator: &AzureTranslator<'_>,
+    query: String,
+    text: &str,
+) -> Result<String, Error> {
+    let key = translator.key.ok_or(Error::NoApiKeySet)?;
+
+    let uri = match query.parse::<Uri>() {
+        Ok(res) => res,
+        Err(_) => return Err(Error::CouldNotParseUri(query)),
+    };
+
+    let body = serde_json::to_string(&[AzureRequestItem { text }])
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+    let mut req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Ocp-Apim-Subscription-Key", key)
+        .header("Content-Type", "application/json");
+    if let Some(region) = translator.region {
+        req = req.header("Ocp-Apim-Subscription-Region", region);
+    }
+    let req = req
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(translator.client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::AzureAPIError(AzureError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of an element of an Azure translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+/// Text translation in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct Translation {
+    text: String,
+    to: String,
+}
+
+/// Serializable struct of an element of an Azure detect response
+#[derive(Debug, Serialize, Deserialize)]
+struct DetectResponse {
+    language: String,
+    score: f64,
+}
+
+impl ApiDetectResponse for DetectResponse {
+    fn get_lang(&self) -> Option<Language> {
+        language_from_code(&self.language)
+    }
+}
+
+/// Serializable struct of an Azure error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error: ErrorContent,
+}
+
+/// Content of an ErrorResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorContent {
+    code: u32,
+    message: String,
+}
+
+/// Enum containing different errors that may be returned by the Microsoft Translator API.
+///
+/// The API returns six-digit error codes, whose first three digits are the HTTP status code.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum AzureError {
+    /// The request is invalid.
+    InvalidRequest,
+    /// The source language is not valid.
+    InvalidSourceLanguage,
+    /// The target language is not valid.
+    InvalidTargetLanguage,
+    /// The text or the request is too long.
+    MaxTextSizeExceeded,
+    /// The subscription key or the region is missing or invalid.
+    InvalidCredentials,
+    /// The operation is not allowed for this subscription.
+    Forbidden,
+    /// The free quota of the subscription has been reached.
+    QuotaExceeded,
+    /// The request timed out on the server side.
+    RequestTimeout,
+    /// Too many requests were sent, the request should be retried later.
+    TooManyRequests,
+    /// An unexpected error happened on the server side.
+    InternalServerError,
+    /// The service is temporarily unavailable.
+    ServiceUnavailable,
+    /// Any other error, with its Azure error code.
+    UnknownErrorCode(u32),
+}
+
+impl AzureError {
+    /// Converts an Azure error code, as found in the body of the error responses, to the enum variant.
+    pub fn from_azure_code(code: u32) -> Self {
+        use AzureError::*;
+        match code {
+            400035 => InvalidSourceLanguage,
+            400036 => InvalidTargetLanguage,
+            400050 | 400077 => MaxTextSizeExceeded,
+            400000..=400999 => InvalidRequest,
+            401000..=401999 => InvalidCredentials,
+            403001 => QuotaExceeded,
+            403000..=403999 => Forbidden,
+            408000..=408999 => RequestTimeout,
+            429000..=429999 => TooManyRequests,
+            500000..=500999 => InternalServerError,
+            503000..=503999 => ServiceUnavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+}
+
+impl ApiError for AzureError {
+    fn from_error_code(code: u16) -> Self {
+        use AzureError::*;
+        match code {
+            400 => InvalidRequest,
+            401 => InvalidCredentials,
+            403 => Forbidden,
+            408 => RequestTimeout,
+            429 => TooManyRequests,
+            500 => InternalServerError,
+            503 => ServiceUnavailable,
+            other => UnknownErrorCode(u32::from(other) * 1000),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use AzureError::*;
+        match self {
+            InvalidRequest
+            | InvalidSourceLanguage
+            | InvalidTargetLanguage
+            | MaxTextSizeExceeded => 400,
+            InvalidCredentials => 401,
+            Forbidden | QuotaExceeded => 403,
+            RequestTimeout => 408,
+            TooManyRequests => 429,
+            InternalServerError => 500,
+            ServiceUnavailable => 503,
+            UnknownErrorCode(other) => (*other / 1000) as u16,
+        }
+    }
+
+    /// Parses the six-digit `code` and the `message` of the error response.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => ProviderError::new(Self::from_azure_code(res.error.code), status)
+                .with_code(&res.error.code.to_string())
+                .with_message(&res.error.message),
+            Err(_) => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use AzureError::*;
+        match self {
+            RequestTimeout | TooManyRequests | InternalServerError | ServiceUnavailable => true,
+            UnknownErrorCode(_) => is_retryable_status(self.to_error_code()),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use AzureError::*;
+        match self {
+            InvalidCredentials | Forbidden => true,
+            UnknownErrorCode(_) => is_auth_status(self.to_error_code()),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use AzureError::*;
+        match self {
+            QuotaExceeded | TooManyRequests => true,
+            UnknownErrorCode(_) => is_quota_status(self.to_error_code()),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use AzureError::*;
+        matches!(self, InvalidSourceLanguage | InvalidTargetLanguage)
+    }
+}
+
+impl std::fmt::Display for AzureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Azure error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for AzureError {}
@@ -0,0 +1,415 @@
+/*!
+A module containing the implementation of the [Baidu Translate API](https://fanyi-api.baidu.com/doc/21).
+
+To use it, see the [`Baidu struct`](struct.Baidu.html).
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the Baidu API.
+pub const BAIDU_BASE_URL: &str = "https://fanyi-api.baidu.com/api/trans/vip";
+
+/// Returns the signature of a request: the MD5 digest of the app ID, the text, the salt and the secret key.
+///
+/// The text must not be URL-encoded.
+pub fn sign(app_id: &str, text: &str, salt: &str, secret_key: &str) -> String {
+    format!(
+        "{:x}",
+        md5::compute(format!("{}{}{}{}", app_id, text, salt, secret_key))
+    )
+}
+
+/// Returns a salt for a request, different for each call.
+fn salt() -> String {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_nanos() % 1_000_000_000)
+        .unwrap_or(0)
+        .to_string()
+}
+
+/// # Baidu Translate API
+///
+/// A struct representing the [Baidu Translate API](https://fanyi-api.baidu.com/doc/21) (百度翻译开放平台).
+///
+/// Each request is signed with an MD5 digest of the app ID, the text, a random salt and the secret key.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Chinese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = Baidu::with_credentials("<BAIDU_APP_ID>", "<BAIDU_SECRET_KEY>");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Chinese)
+///     .await?;
+///
+/// assert_eq!(translated_text, "你好，我叫漩涡鸣人！");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Baidu<'a> {
+    credentials: Option<(&'a str, &'a str)>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> Baidu<'a> {
+    /// Returns a new [`Baidu`](struct.Baidu.html) struct with the given app ID and secret key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_credentials(app_id: &'a str, secret_key: &'a str) -> Self {
+        Self {
+            credentials: Some((app_id, secret_key)),
+            base_url: BAIDU_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`BAIDU_BASE_URL`](constant.BAIDU_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the app ID used by the requests, if set.
+    pub fn get_app_id(&self) -> Option<&'a str> {
+        self.credentials.map(|(app_id, _)| app_id)
+    }
+
+    /// Sends a translation request, from a source language code which may be `auto`.
+    async fn send_translate(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        let (app_id, secret_key) = self.credentials.ok_or(Error::NoApiKeySet)?;
+
+        // build query
+        let salt = salt();
+        let url = format!("{}/translate", self.base_url);
+        let body = format!(
+            "q={}&from={}&to={}&appid={}&salt={}&sign={}",
+            encode(text),
+            source_language,
+            language_code(target_language),
+            encode(app_id),
+            salt,
+            sign(app_id, text, &salt, secret_key)
+        );
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body).await?;
+
+        // errors are returned with a successful HTTP status
+        if from_str::<ErrorResponse>(body.as_str()).is_ok() {
+            return Err(Error::BaiduAPIError(BaiduError::from_response(200, &body)));
+        }
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+#[async_trait]
+impl<'a> Api for Baidu<'a> {
+    /// Returns a new [`Baidu`](struct.Baidu.html) struct without credentials.
+    fn new() -> Self {
+        Self {
+            credentials: None,
+            base_url: BAIDU_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        self.credentials.ok_or(Error::NoApiKeySet)?;
+
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                language_code(source)
+            }
+        };
+
+        retry::run(self.client, || {
+            self.send_translate(&text, source_language, target_language)
+        })
+        .await
+    }
+}
+
+/// Returns the code used by the API for a language.
+fn language_code(language: Language) -> &'static str {
+    use Language::*;
+    match language {
+        Arabic => "ara",
+        Bulgarian => "bul",
+        Danish => "dan",
+        Estonian => "est",
+        Finnish => "fin",
+        French => "fra",
+        Japanese => "jp",
+        Korean => "kor",
+        Romanian => "rom",
+        Slovenian => "slo",
+        Spanish => "spa",
+        Swedish => "swe",
+        Vietnamese => "vie",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::BaiduAPIError(BaiduError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a Baidu translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    trans_result: Vec<TranslationResult>,
+}
+
+/// Translation of a line of text in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslationResult {
+    src: String,
+    dst: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        // each line of the text is translated separately
+        self.trans_result
+            .iter()
+            .map(|result| result.dst.as_str())
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+}
+
+/// Serializable struct of a Baidu error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error_code: String,
+    error_msg: Option<String>,
+}
+
+/// Enum containing different errors that may be returned by the Baidu API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum BaiduError {
+    /// The request timed out, and should be retried.
+    RequestTimeout,
+    /// An internal error happened, the request should be retried.
+    SystemError,
+    /// The app ID is invalid, or the service is not enabled.
+    UnauthorizedUser,
+    /// A required parameter is missing.
+    MissingParameter,
+    /// The signature is invalid, e.g. a wrong secret key.
+    InvalidSign,
+    /// Too many requests were sent, the request should be retried later.
+    AccessFrequencyLimited,
+    /// The balance of the account is insufficient.
+    InsufficientBalance,
+    /// Too many long texts were sent, the request should be retried later.
+    LongQueryTooFrequent,
+    /// The IP address of the client is not allowed.
+    IllegalClientIp,
+    /// The target language is not supported.
+    UnsupportedTargetLanguage,
+    /// The service has been stopped in the console.
+    ServiceClosed,
+    /// The account has not been authenticated.
+    AuthenticationFailed,
+    /// An error code missing from this enum was returned.
+    UnknownBaiduCode,
+    UnknownErrorCode(u16),
+}
+
+impl BaiduError {
+    /// Converts an error code, as found in the `error_code` field of the error responses, to the enum variant.
+    ///
+    /// Returns `None` if the code has no matching variant.
+    pub fn from_baidu_code(code: &str) -> Option<Self> {
+        use BaiduError::*;
+        match code {
+            "52001" => Some(RequestTimeout),
+            "52002" => Some(SystemError),
+            "52003" => Some(UnauthorizedUser),
+            "54000" => Some(MissingParameter),
+            "54001" => Some(InvalidSign),
+            "54003" => Some(AccessFrequencyLimited),
+            "54004" => Some(InsufficientBalance),
+            "54005" => Some(LongQueryTooFrequent),
+            "58000" => Some(IllegalClientIp),
+            "58001" => Some(UnsupportedTargetLanguage),
+            "58002" => Some(ServiceClosed),
+            "90107" => Some(AuthenticationFailed),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for BaiduError {
+    fn from_error_code(code: u16) -> Self {
+        use BaiduError::*;
+        match code {
+            401 => UnauthorizedUser,
+            429 => AccessFrequencyLimited,
+            500 => SystemError,
+            504 => RequestTimeout,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use BaiduError::*;
+        match self {
+            MissingParameter | UnsupportedTargetLanguage => 400,
+            UnauthorizedUser | InvalidSign | AuthenticationFailed => 401,
+            InsufficientBalance => 402,
+            IllegalClientIp | ServiceClosed => 403,
+            AccessFrequencyLimited | LongQueryTooFrequent => 429,
+            SystemError | UnknownBaiduCode => 500,
+            RequestTimeout => 504,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `error_code` and the `error_msg` of the error response, which usually has a `200` status code.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let res = match from_str::<ErrorResponse>(body) {
+            Ok(res) => res,
+            Err(_) => return ProviderError::new(Self::from_error_code(status), status),
+        };
+
+        let kind = Self::from_baidu_code(&res.error_code).unwrap_or_else(|| match status {
+            200 => BaiduError::UnknownBaiduCode,
+            status => Self::from_error_code(status),
+        });
+        let error = ProviderError::new(kind, status).with_code(&res.error_code);
+        match res.error_msg {
+            Some(message) => error.with_message(&message),
+            None => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use BaiduError::*;
+        match self {
+            RequestTimeout | SystemError | AccessFrequencyLimited | LongQueryTooFrequent => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use BaiduError::*;
+        match self {
+            UnauthorizedUser | InvalidSign | IllegalClientIp | ServiceClosed
+            | AuthenticationFailed => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use BaiduError::*;
+        match self {
+            AccessFrequencyLimited | InsufficientBalance | LongQueryTooFrequent => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use BaiduError::*;
+        matches!(self, UnsupportedTargetLanguage)
+    }
+}
+
+impl std::fmt::Display for BaiduError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Baidu error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for BaiduError {}
@@ -0,0 +1,187 @@
+/*!
+A module containing the HTTP client used by the engines to send their requests.
+
+To use it, see the [`HttpClient struct`](struct.HttpClient.html).
+*/
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use http::{Request, Response};
+use hyper::{body::to_bytes, Body};
+
+use super::*;
+
+/// Counter giving a different ID to each new client.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// # HTTP client
+///
+/// A long-lived HTTPS client, keeping a pool of open connections to reuse them between requests.
+///
+/// By default, the engines open a new connection for each request, paying a full TLS handshake each time.
+/// A client can instead be given to one or more engines with their `with_client` method, so that their
+/// requests share its pooled connections and TLS sessions.
+///
+/// Cloning a client is cheap, and the clones share the same pool: they are equal to each other.
+///
+/// The requests are sent through a [`Transport`](trait.Transport.html), a [`HyperTransport`](struct.HyperTransport.html)
+/// by default. With the default transport, the client must be used in a single tokio runtime, as its pooled
+/// connections are tied to the runtime that opened them.
+///
+/// A [`RetryPolicy`](struct.RetryPolicy.html) can be set with [`with_retry`](#method.with_retry), to retry the
+/// requests failing with a transient error, e.g. a rate limiting or an unavailable server.
+///
+/// ### Example
+///
+/// Translate many texts, reusing the same connection:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let client = HttpClient::new();
+/// let translator = DeepL::with_key("<DEEPL_API_KEY>").with_client(&client);
+///
+/// for text in &["Hello!", "How are you?"] {
+///     let translated_text: String = translator
+///         .translate(text.to_string(), InputLanguage::Automatic, Language::French)
+///         .await?;
+///
+///     println!("{}", translated_text);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct HttpClient {
+    id: usize,
+    transport: Arc<dyn Transport>,
+    retry: Option<RetryPolicy>,
+}
+
+impl HttpClient {
+    /// Returns a new client, sending the requests with a [`HyperTransport`](struct.HyperTransport.html)
+    /// trusting the root certificates of the system.
+    ///
+    /// Panics if the TLS backend could not be initialized, see [`HyperTransport::try_new`](struct.HyperTransport.html#method.try_new).
+    pub fn new() -> Self {
+        Self::with_transport(HyperTransport::new())
+    }
+
+    /// Returns a new client, using the given TLS configuration.
+    ///
+    /// Can be used to trust additional root certificates, e.g. the one of a local server.
+    #[cfg(feature = "native-tls")]
+    pub fn from_tls_connector(tls: native_tls::TlsConnector) -> Self {
+        Self::with_transport(HyperTransport::from_tls_connector(tls))
+    }
+
+    /// Returns a new client, using the given rustls configuration.
+    ///
+    /// Can be used to trust additional root certificates, e.g. the one of a local server.
+    #[cfg(feature = "rustls")]
+    pub fn from_tls_config(config: rustls::ClientConfig) -> Self {
+        Self::with_transport(HyperTransport::from_tls_config(config))
+    }
+
+    /// Returns a new client, sending the requests with a [`HyperTransport`](struct.HyperTransport.html)
+    /// using the given timeouts.
+    pub fn with_timeouts(timeouts: Timeouts) -> Self {
+        Self::with_transport(HyperTransport::new().with_timeouts(timeouts))
+    }
+
+    /// Returns a new client, sending the requests with the given [`Transport`](trait.Transport.html).
+    pub fn with_transport<T: Transport + 'static>(transport: T) -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            transport: Arc::new(transport),
+            retry: None,
+        }
+    }
+
+    /// Retries the failed requests as described by the given [`RetryPolicy`](struct.RetryPolicy.html).
+    ///
+    /// By default, the requests are not retried.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    /// Returns the retry policy of the client, if set.
+    pub fn get_retry_policy(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    /// Sends a request with the transport of the client, retrying it if a retry policy is set.
+    pub async fn send(&self, request: Request<Vec<u8>>) -> Result<Response<Vec<u8>>, Error> {
+        match &self.retry {
+            Some(policy) => retry::send(self.transport.as_ref(), policy, request).await,
+            None => self.transport.send(request).await,
+        }
+    }
+}
+
+impl Default for HttpClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for HttpClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("id", &self.id)
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+// clients are compared by identity, so that the engines holding one can still be compared
+
+impl PartialEq for HttpClient {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for HttpClient {}
+
+impl PartialOrd for HttpClient {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HttpClient {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+/// Sends a request with the given client, or with a new one opening a new connection if none is set.
+pub(crate) async fn send(
+    client: Option<&HttpClient>,
+    request: Request<Body>,
+) -> Result<Response<Body>, Error> {
+    // the transports work on buffered bodies
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body)
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let request = Request::from_parts(parts, body.to_vec());
+
+    let response = match client {
+        Some(client) => client.send(request).await?,
+        None => {
+            HttpClient::with_transport(HyperTransport::try_new()?)
+                .send(request)
+                .await?
+        }
+    };
+
+    let (parts, body) = response.into_parts();
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
@@ -0,0 +1,414 @@
+/*!
+A module containing the implementation of the [DeepL API](https://www.deepl.com/docs-api).
+
+To use it, see the [`DeepL struct`](struct.DeepL.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the DeepL API with a free plan key.
+pub const DEEPL_FREE_BASE_URL: &str = "https://api-free.deepl.com/v2";
+
+/// Base URL used to access the DeepL API with a pro plan key.
+pub const DEEPL_PRO_BASE_URL: &str = "https://api.deepl.com/v2";
+
+/// # DeepL API
+///
+/// A struct representing the [DeepL API](https://www.deepl.com/docs-api).
+///
+/// This API needs a key, which can be created from the [DeepL account page](https://www.deepl.com/pro-api).
+/// Keys of the free plan end with `:fx` and are sent to [`DEEPL_FREE_BASE_URL`](constant.DEEPL_FREE_BASE_URL.html),
+/// other keys are sent to [`DEEPL_PRO_BASE_URL`](constant.DEEPL_PRO_BASE_URL.html).
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+/// - API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+///
+/// DeepL has no dedicated detection endpoint: detection translates the text and returns
+/// the `detected_source_language` field of the response.
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to German:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = DeepL::with_key("<DEEPL_API_KEY>:fx");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::German)
+///     .await?;
+///
+/// assert_eq!(translated_text, "Hallo, mein Name ist Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = DeepL::with_key("<DEEPL_API_KEY>:fx");
+/// let text: String = "Bonjour, je m'appelle Naruto Uzumaki!".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::French));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct DeepL<'a> {
+    key: Option<&'a str>,
+    base_url: Option<&'a str>,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> DeepL<'a> {
+    /// Returns a new [`DeepL`](struct.DeepL.html) struct with the given API key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            key: Some(key),
+            base_url: None,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given base URL instead of the free or pro endpoint.
+    ///
+    /// The URL must not end with a slash, e.g. `http://localhost:8080/v2`.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the base URL the requests are sent to.
+    ///
+    /// Unless it was overridden with [`with_base_url`](struct.DeepL.html#method.with_base_url),
+    /// it depends on the plan of the key.
+    pub fn base_url(&self) -> &'a str {
+        match (self.base_url, self.key) {
+            (Some(base_url), _) => base_url,
+            (None, Some(key)) if key.ends_with(":fx") => DEEPL_FREE_BASE_URL,
+            (None, _) => DEEPL_PRO_BASE_URL,
+        }
+    }
+
+    /// Sends a translation request, and returns the first translation of the response.
+    async fn request_translation(
+        &self,
+        text: &str,
+        source_language: Option<Language>,
+        target_language: Language,
+    ) -> Result<Translation, Error> {
+        let key = self.key.ok_or(Error::NoApiKeySet)?;
+
+        // build query
+        let url = format!("{}/translate", self.base_url());
+        let mut body = format!(
+            "text={}&target_lang={}",
+            encode(text),
+            target_language_code(target_language)
+        );
+        if let Some(source) = source_language {
+            body = format!("{}&source_lang={}", body, source_language_code(source));
+        }
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, key, body).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        json_body
+            .translations
+            .into_iter()
+            .next()
+            .ok_or(Error::CouldNotDerializeJson)
+    }
+}
+
+impl<'a> ApiKey<'a> for DeepL<'a> {
+    fn set_set(&mut self, key: &'a str) {
+        self.key = Some(key)
+    }
+
+    fn get_key(&self) -> Option<&'a str> {
+        self.key
+    }
+}
+
+#[async_trait]
+impl<'a> Api for DeepL<'a> {
+    /// Returns a new [`DeepL`](struct.DeepL.html) struct without API key.
+    ///
+    /// To set it, use [`with_key`](struct.DeepL.html#method.with_key) or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
+    fn new() -> Self {
+        Self {
+            key: None,
+            base_url: None,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => None,
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                Some(source)
+            }
+        };
+
+        let translation = self
+            .request_translation(&text, source_language, target_language)
+            .await?;
+
+        Ok(translation.get_text())
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for DeepL<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        let translation = self
+            .request_translation(&text, None, Language::English)
+            .await?;
+
+        Ok(translation.get_lang())
+    }
+}
+
+/// Returns the code used by DeepL for a source language.
+fn source_language_code(language: Language) -> String {
+    language.to_language_code().to_uppercase()
+}
+
+/// Returns the code used by DeepL for a target language.
+///
+/// English and Portuguese need a regional variant when used as target.
+fn target_language_code(language: Language) -> String {
+    match language {
+        Language::English => "EN-US".to_string(),
+        Language::Portuguese => "PT-PT".to_string(),
+        other => source_language_code(other),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    key: &str,
+    body: String,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Authorization", format!("DeepL-Auth-Key {}", key))
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::DeepLAPIError(DeepLError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a DeepL translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+/// Text translation in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct Translation {
+    detected_source_language: String,
+    text: String,
+}
+
+impl ApiTranslateResponse for Translation {
+    fn get_text(&self) -> String {
+        self.text.clone()
+    }
+}
+
+impl ApiDetectResponse for Translation {
+    fn get_lang(&self) -> Option<Language> {
+        Language::from_language_code(&self.detected_source_language.to_lowercase())
+    }
+}
+
+/// Serializable struct of a DeepL error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    message: String,
+    detail: Option<String>,
+}
+
+/// Enum containing different errors that may be returned by the DeepL API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum DeepLError {
+    /// Bad request, e.g. a missing parameter or an unsupported language.
+    BadRequest,
+    /// The API key is invalid, or does not match the endpoint.
+    AuthorizationFailed,
+    /// The requested resource could not be found.
+    NotFound,
+    /// The request size exceeds the limit.
+    RequestTooLarge,
+    /// The request URL is too long.
+    UriTooLong,
+    /// Too many requests were sent, the request should be retried later.
+    TooManyRequests,
+    /// The character limit of the plan has been reached.
+    QuotaExceeded,
+    /// The service is temporarily unavailable.
+    ServiceUnavailable,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for DeepLError {
+    fn from_error_code(code: u16) -> Self {
+        use DeepLError::*;
+        match code {
+            400 => BadRequest,
+            403 => AuthorizationFailed,
+            404 => NotFound,
+            413 => RequestTooLarge,
+            414 => UriTooLong,
+            429 | 529 => TooManyRequests,
+            456 => QuotaExceeded,
+            503 => ServiceUnavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use DeepLError::*;
+        match self {
+            BadRequest => 400,
+            AuthorizationFailed => 403,
+            NotFound => 404,
+            RequestTooLarge => 413,
+            UriTooLong => 414,
+            TooManyRequests => 429,
+            QuotaExceeded => 456,
+            ServiceUnavailable => 503,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `message` of the error response, followed by its `detail` if any.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let error = ProviderError::new(Self::from_error_code(status), status);
+        match from_str::<ErrorResponse>(body) {
+            Ok(ErrorResponse {
+                message,
+                detail: Some(detail),
+            }) => error.with_message(&format!("{} {}", message, detail)),
+            Ok(ErrorResponse { message, .. }) => error.with_message(&message),
+            Err(_) => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use DeepLError::*;
+        match self {
+            TooManyRequests | ServiceUnavailable => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use DeepLError::*;
+        match self {
+            AuthorizationFailed => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use DeepLError::*;
+        match self {
+            TooManyRequests | QuotaExceeded => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// DeepL reports the unsupported languages as bad requests, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for DeepLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DeepL error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for DeepLError {}
@@ -7,8 +7,7 @@ To use it, see the [`GoogleV2 struct`](struct.GoogleV2.html).
 use async_trait::async_trait;
 
 use http::{uri::Uri, Request};
-use hyper::{body::to_bytes, client::Client, Body};
-use hyper_tls::HttpsConnector;
+use hyper::{body::to_bytes, Body};
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
 
@@ -17,17 +16,25 @@ use super::*;
 /// Base URL used to access the Google API.
 pub const GOOGLE_V2_BASE_URL: &str = "https://translation.googleapis.com/language/translate/v2";
 
-/// Helper structure of the request boy of a google translate request
+/// Maximum number of texts sent in a single request by [`translate_batch`](../trait.Api.html#method.translate_batch).
+pub const GOOGLE_V2_MAX_BATCH_TEXTS: usize = 128;
+
+/// Maximum number of characters sent in a single request by [`translate_batch`](../trait.Api.html#method.translate_batch),
+/// the recommended maximum of the API.
+pub const GOOGLE_V2_MAX_BATCH_CHARACTERS: usize = 5000;
+
+/// Helper structure of the request boy of a google translate request,
+/// `q` being either a text or a list of texts
 #[derive(Serialize)]
-struct GoogleV2RequestBody<'a> {
-    q: &'a str,
+struct GoogleV2RequestBody<'a, Q> {
+    q: Q,
     source: Option<&'a str>,
     target: &'a str,
     format: &'static str,
 }
 
-impl<'a> GoogleV2RequestBody<'a> {
-    fn new(q: &'a str, source: Option<&'a str>, target: &'a str) -> Self {
+impl<'a, Q: Serialize> GoogleV2RequestBody<'a, Q> {
+    fn new(q: Q, source: Option<&'a str>, target: &'a str) -> Self {
         Self {
             q,
             source,
@@ -45,7 +52,8 @@ impl<'a> GoogleV2RequestBody<'a> {
 ///
 /// It implements:
 ///
-/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language translation, with the default [`Api`](../trait.Api.html) trait, translating up to
+///   [`GOOGLE_V2_MAX_BATCH_TEXTS`](constant.GOOGLE_V2_MAX_BATCH_TEXTS.html) texts per request with `translate_batch`
 /// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
 /// - API key, with the [`ApiKey`](../trait.ApiDetect.html) trait
 ///
@@ -96,6 +104,8 @@ impl<'a> GoogleV2RequestBody<'a> {
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 pub struct GoogleV2<'a> {
     key: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
 }
 
 impl<'a> GoogleV2<'a> {
@@ -103,7 +113,26 @@ impl<'a> GoogleV2<'a> {
     ///
     /// Can be used in constant definitions.
     pub const fn with_key(key: &'a str) -> Self {
-        Self { key: Some(key) }
+        Self {
+            key: Some(key),
+            base_url: GOOGLE_V2_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`GOOGLE_V2_BASE_URL`](constant.GOOGLE_V2_BASE_URL.html),
+    /// e.g. a regional endpoint, a proxy or a local mock server.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
     }
 }
 
@@ -123,7 +152,11 @@ impl<'a> Api for GoogleV2<'a> {
     ///
     /// To set it, use [`with_key`](struct.Google.html#method.with_key) or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
     fn new() -> Self {
-        Self { key: None }
+        Self {
+            key: None,
+            base_url: GOOGLE_V2_BASE_URL,
+            client: None,
+        }
     }
 
     // TODO make `translate` async
@@ -133,27 +166,80 @@ impl<'a> Api for GoogleV2<'a> {
         source_language: InputLanguage,
         target_language: Language,
     ) -> Result<String, Error> {
-        // get translation direction
-        let source_language = match source_language {
-            InputLanguage::Automatic => None,
-            InputLanguage::Defined(source) => {
-                // verify that source languages != target language
-                if source == target_language {
-                    return Err(Error::SameLanguages(source, target_language));
+        let source_language = translation_source(source_language, target_language)?;
+
+        let json_body = self
+            .send_translate(text.as_str(), source_language, target_language)
+            .await?;
+
+        Ok(json_body.get_text())
+    }
+
+    fn batch_limits(&self) -> Option<(usize, usize)> {
+        Some((GOOGLE_V2_MAX_BATCH_TEXTS, GOOGLE_V2_MAX_BATCH_CHARACTERS))
+    }
+
+    /// Translates the texts with a request per [`GOOGLE_V2_MAX_BATCH_TEXTS`](constant.GOOGLE_V2_MAX_BATCH_TEXTS.html)
+    /// texts, repeating the `q` parameter.
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<Vec<String>, Error> {
+        let source_language = translation_source(source_language, target_language)?;
+        self.key.ok_or(Error::NoApiKeySet)?;
+
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in batch_chunks(
+            &texts,
+            GOOGLE_V2_MAX_BATCH_TEXTS,
+            GOOGLE_V2_MAX_BATCH_CHARACTERS,
+        ) {
+            let translations = match self
+                .send_translate(chunk, source_language, target_language)
+                .await
+            {
+                Ok(json_body) if json_body.data.translations.len() == chunk.len() => {
+                    json_body.data.translations
+                }
+                Ok(_) => {
+                    results.extend(chunk.iter().map(|_| Err(Error::CouldNotDerializeJson)));
+                    continue;
+                }
+                Err(err) => {
+                    results.extend(chunk.iter().map(|_| Err(err.clone())));
+                    continue;
                 }
+            };
 
-                Some(source.to_language_code())
-            }
-        };
+            results.extend(
+                translations
+                    .into_iter()
+                    .map(|translation| Ok(translation.translated_text)),
+            );
+        }
+
+        batch_results(results)
+    }
+}
 
+impl<'a> GoogleV2<'a> {
+    /// Sends a translation request of the given text or list of texts.
+    async fn send_translate<Q: Serialize>(
+        &self,
+        q: Q,
+        source_language: Option<&str>,
+        target_language: Language,
+    ) -> Result<TranslateResponse, Error> {
         // build query
         let url: String = format!(
             "{}?key={}",
-            GOOGLE_V2_BASE_URL,
+            self.base_url,
             self.key.ok_or(Error::NoApiKeySet)?
         );
         let body = serde_json::to_string(&GoogleV2RequestBody::new(
-            &text,
+            q,
             source_language,
             target_language.to_language_code(),
         ))
@@ -164,14 +250,30 @@ impl<'a> Api for GoogleV2<'a> {
             Err(_) => return Err(Error::CouldNotParseUri(url)),
         };
 
-        let body = get_response(uri, body).await?;
+        let body = get_response(self.client, uri, body).await?;
 
-        let json_body: TranslateResponse = match from_str(body.as_str()) {
-            Ok(res) => res,
-            Err(_) => return Err(Error::CouldNotDerializeJson),
-        };
+        match from_str(body.as_str()) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(Error::InvalidResponse(ErrorSource::new(err))),
+        }
+    }
+}
 
-        Ok(json_body.get_text())
+/// Returns the source language code of a translation, or `None` to detect it.
+fn translation_source(
+    source_language: InputLanguage,
+    target_language: Language,
+) -> Result<Option<&'static str>, Error> {
+    match source_language {
+        InputLanguage::Automatic => Ok(None),
+        InputLanguage::Defined(source) => {
+            // verify that source languages != target language
+            if source == target_language {
+                return Err(Error::SameLanguages(source, target_language));
+            }
+
+            Ok(Some(source.to_language_code()))
+        }
     }
 }
 
@@ -182,7 +284,7 @@ impl<'a> ApiDetect for GoogleV2<'a> {
         // build query
         let query = format!(
             "{}/detect?key={}",
-            GOOGLE_V2_BASE_URL,
+            self.base_url,
             match self.key {
                 Some(key) => key,
                 None => return Err(Error::NoApiKeySet),
@@ -196,11 +298,11 @@ impl<'a> ApiDetect for GoogleV2<'a> {
             Err(_) => return Err(Error::CouldNotParseUri(query)),
         };
 
-        let body = get_response(uri, body).await?;
+        let body = get_response(self.client, uri, body).await?;
 
         let json_body: GoogleDetectResponse = match from_str(body.as_str()) {
             Ok(res) => res,
-            Err(_) => return Err(super::Error::CouldNotDerializeJson),
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
         };
 
         Ok(json_body.get_lang())
@@ -208,36 +310,33 @@ impl<'a> ApiDetect for GoogleV2<'a> {
 }
 
 /// Returns the response json body, needed to be deserialized.
-async fn get_response(uri: Uri, body: String) -> Result<String, Error> {
-    let https = HttpsConnector::new();
-    let client = Client::builder().build::<_, hyper::Body>(https);
-
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+) -> Result<String, Error> {
     let req = Request::builder()
         .method("POST")
         .uri(uri)
         .body(Body::from(body))
-        .expect("request builder");
-
-    let res = client
-        .request(req)
-        .await
         .map_err(|e| Error::RequestError(e.to_string()))?;
 
-    match res.status().as_u16() {
-        200 => (),
-        error => {
-            return Err(Error::GoogleV2APIError(GoogleV2Error::from_error_code(
-                error,
-            )))
-        }
-    };
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
 
     let body = to_bytes(res.into_body())
         .await
-        .map_err(|e| Error::RequestError(e.to_string()))?;
-    match std::str::from_utf8(&body) {
-        Ok(res) => Ok(res.to_string()),
-        Err(err) => Err(Error::CouldNotConvertToUtf8Str(err)),
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::GoogleV2APIError(GoogleV2Error::from_response(
+            status, &body,
+        ))),
     }
 }
 
@@ -297,7 +396,7 @@ impl ApiDetectResponse for GoogleDetectResponse {
                 .data
                 .detections
                 .iter()
-                .map(|detection| detection.first().unwrap())
+                .filter_map(|detection| detection.first())
                 .map(|detection| &detection.language[..])
                 .collect::<Vec<&str>>()
                 .join("\n"),
@@ -305,47 +404,173 @@ impl ApiDetectResponse for GoogleDetectResponse {
     }
 }
 
+/// Serializable struct of a Google error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error: ErrorContent,
+}
+
+/// Content of an ErrorResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorContent {
+    message: String,
+    #[serde(default)]
+    errors: Vec<ErrorReason>,
+    status: Option<String>,
+    #[serde(default)]
+    details: Vec<ErrorReason>,
+}
+
+/// Reason of an error in an ErrorContent, either a legacy error or an `ErrorInfo` detail
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorReason {
+    reason: Option<String>,
+}
+
 /// Enum containing different errors that may be returned by the Google API.
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 pub enum GoogleV2Error {
+    /// The API key is invalid or expired.
     InvalidAPIKey,
+    /// The API key is not allowed to use the API, e.g. a restricted key or a project with the API disabled.
     BlockedAPIKey,
+    /// The daily quota of the project has been exceeded.
     DailyLimitExceeded,
+    /// Too many requests were sent, the request should be retried later.
+    RateLimitExceeded,
+    /// The text sent is too long.
     MaxTextSizeExceeded,
+    /// The request is invalid, e.g. a missing parameter or an unsupported language.
+    InvalidRequest,
+    /// The API failed to translate the text, e.g. a backend error.
     CouldNotTranslate,
+    /// The pair of languages is not supported.
     TranslationDirectionNotSupported,
     UnknownErrorCode(u16),
 }
 
+impl GoogleV2Error {
+    /// Converts an error reason, as found in the `errors` and `details` of the error responses, to the enum variant.
+    ///
+    /// Returns `None` if the reason has no matching variant, e.g. the generic `invalid` or `badRequest` reasons.
+    pub fn from_reason(reason: &str) -> Option<Self> {
+        use GoogleV2Error::*;
+        match reason {
+            "keyInvalid" | "keyExpired" | "API_KEY_INVALID" => Some(InvalidAPIKey),
+            "forbidden"
+            | "accessNotConfigured"
+            | "ipRefererBlocked"
+            | "SERVICE_DISABLED"
+            | "API_KEY_SERVICE_BLOCKED" => Some(BlockedAPIKey),
+            "dailyLimitExceeded" | "quotaExceeded" => Some(DailyLimitExceeded),
+            "userRateLimitExceeded" | "rateLimitExceeded" | "RATE_LIMIT_EXCEEDED" => {
+                Some(RateLimitExceeded)
+            }
+            "backendError" => Some(CouldNotTranslate),
+            _ => None,
+        }
+    }
+}
+
 impl ApiError for GoogleV2Error {
     fn from_error_code(code: u16) -> Self {
-        match code - 1 {
-            401 => GoogleV2Error::InvalidAPIKey,
-            402 => GoogleV2Error::BlockedAPIKey,
-            404 => GoogleV2Error::DailyLimitExceeded,
-            413 => GoogleV2Error::MaxTextSizeExceeded,
-            422 => GoogleV2Error::CouldNotTranslate,
-            501 => GoogleV2Error::TranslationDirectionNotSupported,
-            other => GoogleV2Error::UnknownErrorCode(other),
+        use GoogleV2Error::*;
+        match code {
+            400 => InvalidRequest,
+            401 => InvalidAPIKey,
+            403 => BlockedAPIKey,
+            413 => MaxTextSizeExceeded,
+            429 => RateLimitExceeded,
+            500 | 503 => CouldNotTranslate,
+            other => UnknownErrorCode(other),
         }
     }
 
     fn to_error_code(&self) -> u16 {
+        use GoogleV2Error::*;
+        match self {
+            InvalidAPIKey | InvalidRequest | TranslationDirectionNotSupported => 400,
+            BlockedAPIKey | DailyLimitExceeded => 403,
+            MaxTextSizeExceeded => 413,
+            RateLimitExceeded => 429,
+            CouldNotTranslate => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `error` of the response, whose most specific reason is kept as the provider code.
+    ///
+    /// The generic `invalid` reason is refined with the message, e.g. `Bad language pair: en|xx`.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let error = match from_str::<ErrorResponse>(body) {
+            Ok(res) => res.error,
+            Err(_) => return ProviderError::new(Self::from_error_code(status), status),
+        };
+
+        // the `ErrorInfo` details are more specific than the legacy errors
+        let reasons: Vec<&str> = error
+            .details
+            .iter()
+            .chain(error.errors.iter())
+            .filter_map(|error| error.reason.as_deref())
+            .collect();
+        let kind = reasons
+            .iter()
+            .find_map(|reason| Self::from_reason(reason))
+            .or_else(|| {
+                if error.message.starts_with("Bad language pair") {
+                    Some(GoogleV2Error::TranslationDirectionNotSupported)
+                } else if error.message.starts_with("Text too long") {
+                    Some(GoogleV2Error::MaxTextSizeExceeded)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or_else(|| Self::from_error_code(status));
+
+        let res = ProviderError::new(kind, status).with_message(&error.message);
+        match reasons.first().copied().or(error.status.as_deref()) {
+            Some(code) => res.with_code(code),
+            None => res,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use GoogleV2Error::*;
+        match self {
+            RateLimitExceeded | CouldNotTranslate => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use GoogleV2Error::*;
         match self {
-            GoogleV2Error::InvalidAPIKey => 401,
-            GoogleV2Error::BlockedAPIKey => 402,
-            GoogleV2Error::DailyLimitExceeded => 404,
-            GoogleV2Error::MaxTextSizeExceeded => 413,
-            GoogleV2Error::CouldNotTranslate => 422,
-            GoogleV2Error::TranslationDirectionNotSupported => 501,
-            GoogleV2Error::UnknownErrorCode(other) => *other,
+            InvalidAPIKey | BlockedAPIKey => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
         }
     }
+
+    fn is_quota(&self) -> bool {
+        use GoogleV2Error::*;
+        match self {
+            DailyLimitExceeded | RateLimitExceeded => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use GoogleV2Error::*;
+        matches!(self, TranslationDirectionNotSupported)
+    }
 }
 
 impl std::fmt::Display for GoogleV2Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error : {}", &self)
+        write!(f, "Google v2 error {} : {:?}", self.to_error_code(), self)
     }
 }
 
@@ -0,0 +1,676 @@
+/*!
+A module containing the implementation of the [Google Cloud Translation API v3 (Advanced)](https://cloud.google.com/translate/docs/advanced/translating-text-v3).
+
+To use it, see the [`GoogleV3 struct`](struct.GoogleV3.html).
+*/
+
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+use super::*;
+
+/// Base URL used to access the Google API.
+pub const GOOGLE_V3_BASE_URL: &str = "https://translation.googleapis.com/v3";
+
+/// URL used to exchange a signed JWT for an access token, when the service account does not define one.
+pub const GOOGLE_TOKEN_URI: &str = "https://oauth2.googleapis.com/token";
+
+/// OAuth scope requested for the access tokens.
+const SCOPE: &str = "https://www.googleapis.com/auth/cloud-translation";
+
+/// Lifetime requested for the signed JWT, in seconds.
+const JWT_LIFETIME: u64 = 3600;
+
+/// Margin removed from the lifetime of the access tokens, so that they are renewed before expiring.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(60);
+
+/// A Google Cloud service account, as found in the JSON key file downloaded from the console.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ServiceAccount {
+    /// ID of the project the account belongs to.
+    pub project_id: String,
+    /// ID of the private key, sent in the header of the signed JWT.
+    #[serde(default)]
+    pub private_key_id: Option<String>,
+    /// PEM-encoded RSA private key.
+    pub private_key: String,
+    /// Email address of the account.
+    pub client_email: String,
+    /// URL used to exchange the signed JWT for an access token.
+    #[serde(default = "default_token_uri")]
+    pub token_uri: String,
+}
+
+fn default_token_uri() -> String {
+    GOOGLE_TOKEN_URI.to_string()
+}
+
+impl ServiceAccount {
+    /// Parses the content of a service account JSON key file.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        from_str(json).map_err(|err| Error::CouldNotParseCredentials(ErrorSource::new(err)))
+    }
+
+    /// Reads and parses a service account JSON key file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path).map_err(|err| {
+            Error::InvalidCredentials(format!("could not read {} : {}", path.display(), err))
+        })?;
+
+        Self::from_json(&json)
+    }
+}
+
+/// Claims of the JWT exchanged for an access token
+#[derive(Serialize)]
+struct Claims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: u64,
+    exp: u64,
+}
+
+/// Access token cached until it expires
+#[derive(Debug)]
+struct AccessToken {
+    token: String,
+    expires_at: Instant,
+}
+
+/// Helper structure of the request body of a Google v3 translate request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateRequestBody<'a> {
+    contents: [&'a str; 1],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_language_code: Option<&'a str>,
+    target_language_code: &'a str,
+    mime_type: &'static str,
+}
+
+/// Helper structure of the request body of a Google v3 detect request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectRequestBody<'a> {
+    content: &'a str,
+    mime_type: &'static str,
+}
+
+/// # Google Cloud Translation API v3
+///
+/// A struct representing the [Google Cloud Translation API v3 (Advanced)](https://cloud.google.com/translate/docs/advanced/translating-text-v3).
+///
+/// Unlike [`GoogleV2`](struct.GoogleV2.html), this API is authorized with a [service account](https://cloud.google.com/iam/docs/service-accounts):
+/// a JWT is signed with its private key and exchanged for an OAuth access token, which is cached until it expires.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+///
+/// To use it, first construct the struct from the JSON key file of a service account, then do the desired function calls.
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to German:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = GoogleV3::from_service_account_file("service-account.json")?.with_location("us-central1");
+///
+/// let text: String = "There is no real ending. It's just the place where you stop the story.".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::German)
+///     .await?;
+///
+/// assert_eq!(translated_text, "Es gibt kein wirkliches Ende. Es ist nur der Ort, an dem Sie die Geschichte stoppen.");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = GoogleV3::from_service_account_file("service-account.json")?;
+/// let text: String = "Es gibt kein wirkliches Ende.".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::German));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct GoogleV3 {
+    account: Option<ServiceAccount>,
+    project_id: Option<String>,
+    location: String,
+    base_url: String,
+    token_uri: Option<String>,
+    token: Mutex<Option<AccessToken>>,
+    client: Option<HttpClient>,
+}
+
+impl GoogleV3 {
+    /// Returns a new [`GoogleV3`](struct.GoogleV3.html) struct using the given service account.
+    pub fn with_service_account(account: ServiceAccount) -> Self {
+        Self {
+            account: Some(account),
+            ..Self::new()
+        }
+    }
+
+    /// Returns a new [`GoogleV3`](struct.GoogleV3.html) struct using the service account of the given JSON key file.
+    pub fn from_service_account_file<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Ok(Self::with_service_account(ServiceAccount::from_file(path)?))
+    }
+
+    /// Sets the project used by the requests, instead of the project of the service account.
+    pub fn with_project_id(mut self, project_id: &str) -> Self {
+        self.project_id = Some(project_id.to_string());
+        self
+    }
+
+    /// Sets the location used by the requests, `global` by default.
+    pub fn with_location(mut self, location: &str) -> Self {
+        self.location = location.to_string();
+        self
+    }
+
+    /// Sends the requests to the given base URL instead of [`GOOGLE_V3_BASE_URL`](constant.GOOGLE_V3_BASE_URL.html).
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.to_string();
+        self
+    }
+
+    /// Exchanges the signed JWT at the given URL, instead of the `token_uri` of the service account.
+    pub fn with_token_uri(mut self, token_uri: &str) -> Self {
+        self.token_uri = Some(token_uri.to_string());
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub fn with_client(mut self, client: &HttpClient) -> Self {
+        self.client = Some(client.clone());
+        self
+    }
+
+    /// Returns the service account used by the requests, if set.
+    pub fn get_service_account(&self) -> Option<&ServiceAccount> {
+        self.account.as_ref()
+    }
+
+    /// Returns the URL of a method of the API, e.g. `translateText`.
+    fn method_url(&self, method: &str) -> Result<String, Error> {
+        let project_id = match (&self.project_id, &self.account) {
+            (Some(project_id), _) => project_id,
+            (None, Some(account)) => &account.project_id,
+            (None, None) => return Err(Error::NoApiKeySet),
+        };
+
+        Ok(format!(
+            "{}/projects/{}/locations/{}:{}",
+            self.base_url, project_id, self.location, method
+        ))
+    }
+
+    /// Locks the cached access token, ignoring a poisoned lock since the cache is always in a valid state.
+    fn cached_token(&self) -> MutexGuard<'_, Option<AccessToken>> {
+        match self.token.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    /// Returns a valid access token, requesting a new one if the cached token expired.
+    async fn access_token(&self) -> Result<String, Error> {
+        if let Some(token) = self.cached_token().as_ref() {
+            if token.expires_at > Instant::now() {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let account = self.account.as_ref().ok_or(Error::NoApiKeySet)?;
+        let token_uri = self.token_uri.as_ref().unwrap_or(&account.token_uri);
+
+        // sign the JWT
+        let iat = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let claims = Claims {
+            iss: &account.client_email,
+            scope: SCOPE,
+            aud: token_uri,
+            iat,
+            exp: iat + JWT_LIFETIME,
+        };
+        let mut header = Header::new(Algorithm::RS256);
+        header.kid = account.private_key_id.clone();
+        let key = EncodingKey::from_rsa_pem(account.private_key.as_bytes())
+            .map_err(|err| Error::InvalidCredentials(err.to_string()))?;
+        let jwt = encode(&header, &claims, &key)
+            .map_err(|err| Error::InvalidCredentials(err.to_string()))?;
+
+        // exchange it for an access token
+        let body = format!(
+            "grant_type={}&assertion={}",
+            urlencoding::encode("urn:ietf:params:oauth:grant-type:jwt-bearer"),
+            jwt
+        );
+        let (status, body) = get_response(
+            self.client.as_ref(),
+            token_uri.clone(),
+            "application/x-www-form-urlencoded",
+            None,
+            body,
+        )
+        .await?;
+
+        if status != 200 {
+            return Err(Error::GoogleV3APIError(GoogleV3Error::from_response(
+                status, &body,
+            )));
+        }
+
+        let json_body: TokenResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        let expires_at = Instant::now()
+            + Duration::from_secs(json_body.expires_in).saturating_sub(TOKEN_EXPIRY_MARGIN);
+        *self.cached_token() = Some(AccessToken {
+            token: json_body.access_token.clone(),
+            expires_at,
+        });
+
+        Ok(json_body.access_token)
+    }
+
+    /// Sends an authorized request to a method of the API, and returns the response json body.
+    async fn call(&self, method: &str, body: String) -> Result<String, Error> {
+        let url = self.method_url(method)?;
+        let token = self.access_token().await?;
+
+        let (status, body) = get_response(
+            self.client.as_ref(),
+            url,
+            "application/json",
+            Some(format!("Bearer {}", token)),
+            body,
+        )
+        .await?;
+
+        match status {
+            200 => Ok(body),
+            status => {
+                if status == 401 {
+                    // the token may have been revoked, request a new one next time
+                    *self.cached_token() = None;
+                }
+
+                Err(Error::GoogleV3APIError(GoogleV3Error::from_response(
+                    status, &body,
+                )))
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Api for GoogleV3 {
+    /// Returns a new [`GoogleV3`](struct.GoogleV3.html) struct without service account.
+    ///
+    /// To set it, use [`with_service_account`](struct.GoogleV3.html#method.with_service_account) or
+    /// [`from_service_account_file`](struct.GoogleV3.html#method.from_service_account_file) instead.
+    fn new() -> Self {
+        Self {
+            account: None,
+            project_id: None,
+            location: "global".to_string(),
+            base_url: GOOGLE_V3_BASE_URL.to_string(),
+            token_uri: None,
+            token: Mutex::new(None),
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => None,
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                Some(language_code(source))
+            }
+        };
+
+        let body = serde_json::to_string(&TranslateRequestBody {
+            contents: [&text],
+            source_language_code: source_language,
+            target_language_code: language_code(target_language),
+            mime_type: "text/plain",
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let body = self.call("translateText", body).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+#[async_trait]
+impl ApiDetect for GoogleV3 {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        let body = serde_json::to_string(&DetectRequestBody {
+            content: &text,
+            mime_type: "text/plain",
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let body = self.call("detectLanguage", body).await?;
+
+        let json_body: DetectResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_lang())
+    }
+}
+
+/// Returns the code used by Google for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CN",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the response status code and body.
+async fn get_response(
+    client: Option<&HttpClient>,
+    url: String,
+    content_type: &str,
+    authorization: Option<String>,
+    body: String,
+) -> Result<(u16, String), Error> {
+    let uri = match url.parse::<Uri>() {
+        Ok(res) => res,
+        Err(_) => return Err(Error::CouldNotParseUri(url)),
+    };
+
+    let mut req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", content_type);
+    if let Some(authorization) = authorization {
+        req = req.header("Authorization", authorization);
+    }
+    let req = req
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    match std::str::from_utf8(&body) {
+        Ok(res) => Ok((status, res.to_string())),
+        Err(err) => Err(Error::CouldNotConvertToUtf8Str(err)),
+    }
+}
+
+/// Serializable struct of an OAuth token response
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// Serializable struct of an OAuth token error response
+#[derive(Debug, Serialize, Deserialize)]
+struct TokenErrorResponse {
+    error: String,
+    error_description: Option<String>,
+}
+
+/// Serializable struct of a Google v3 translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+/// Text translation in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Translation {
+    translated_text: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.translations
+            .iter()
+            .map(|translation| &translation.translated_text[..])
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+}
+
+/// Serializable struct of a Google v3 detect response
+#[derive(Debug, Serialize, Deserialize)]
+struct DetectResponse {
+    languages: Vec<Detection>,
+}
+
+/// Language detected in a DetectResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Detection {
+    language_code: String,
+    confidence: f64,
+}
+
+impl ApiDetectResponse for DetectResponse {
+    fn get_lang(&self) -> Option<Language> {
+        let code = &self.languages.first()?.language_code;
+
+        match code.as_str() {
+            "zh-CN" | "zh-TW" => Some(Language::Chinese),
+            "iw" => Some(Language::Hebrew),
+            code => Language::from_language_code(code),
+        }
+    }
+}
+
+/// Serializable struct of a Google API error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error: ErrorContent,
+}
+
+/// Content of an ErrorResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorContent {
+    code: u16,
+    message: String,
+    status: String,
+}
+
+/// Enum containing different errors that may be returned by the Google v3 API or its token endpoint.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum GoogleV3Error {
+    /// The request is invalid, e.g. an unsupported language.
+    InvalidArgument,
+    /// The access token is missing, invalid or expired.
+    Unauthenticated,
+    /// The service account is not allowed to use the API or the project.
+    PermissionDenied,
+    /// The project or location does not exist.
+    NotFound,
+    /// A quota has been exceeded.
+    ResourceExhausted,
+    /// An unexpected error happened on the server side.
+    Internal,
+    /// The service is temporarily unavailable.
+    Unavailable,
+    /// The token endpoint rejected the signed JWT, e.g. because the key was revoked.
+    InvalidGrant,
+    UnknownErrorCode(u16),
+}
+
+impl GoogleV3Error {
+    /// Converts the `status` field of an error response to the enum variant.
+    ///
+    /// Returns `None` if the status has no matching variant.
+    pub fn from_status(status: &str) -> Option<Self> {
+        use GoogleV3Error::*;
+        match status {
+            "INVALID_ARGUMENT" | "FAILED_PRECONDITION" => Some(InvalidArgument),
+            "UNAUTHENTICATED" => Some(Unauthenticated),
+            "PERMISSION_DENIED" => Some(PermissionDenied),
+            "NOT_FOUND" => Some(NotFound),
+            "RESOURCE_EXHAUSTED" => Some(ResourceExhausted),
+            "INTERNAL" => Some(Internal),
+            "UNAVAILABLE" => Some(Unavailable),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for GoogleV3Error {
+    fn from_error_code(code: u16) -> Self {
+        use GoogleV3Error::*;
+        match code {
+            400 => InvalidArgument,
+            401 => Unauthenticated,
+            403 => PermissionDenied,
+            404 => NotFound,
+            429 => ResourceExhausted,
+            500 => Internal,
+            503 => Unavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use GoogleV3Error::*;
+        match self {
+            InvalidArgument | InvalidGrant => 400,
+            Unauthenticated => 401,
+            PermissionDenied => 403,
+            NotFound => 404,
+            ResourceExhausted => 429,
+            Internal => 500,
+            Unavailable => 503,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `status` and the `message` of an API error response,
+    /// or the `error` and the `error_description` of a token endpoint error response.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        if let Ok(res) = from_str::<ErrorResponse>(body) {
+            let kind = Self::from_status(&res.error.status)
+                .unwrap_or_else(|| Self::from_error_code(status));
+            return ProviderError::new(kind, status)
+                .with_code(&res.error.status)
+                .with_message(&res.error.message);
+        }
+
+        match from_str::<TokenErrorResponse>(body) {
+            Ok(res) => {
+                let kind = match res.error.as_str() {
+                    "invalid_grant" => GoogleV3Error::InvalidGrant,
+                    _ => Self::from_error_code(status),
+                };
+                let error = ProviderError::new(kind, status).with_code(&res.error);
+                match res.error_description {
+                    Some(description) => error.with_message(&description),
+                    None => error,
+                }
+            }
+            Err(_) => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use GoogleV3Error::*;
+        match self {
+            ResourceExhausted | Internal | Unavailable => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use GoogleV3Error::*;
+        match self {
+            Unauthenticated | PermissionDenied | InvalidGrant => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use GoogleV3Error::*;
+        match self {
+            ResourceExhausted => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// Google reports the unsupported languages as invalid arguments, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for GoogleV3Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Google error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for GoogleV3Error {}
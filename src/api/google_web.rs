@@ -0,0 +1,382 @@
+/*!
+A module containing the implementation of the keyless Google Translate web endpoint.
+
+This module is only available with the `keyless` cargo feature.
+
+To use it, see the [`GoogleWeb struct`](struct.GoogleWeb.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde_json::{from_str, Value};
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL of the keyless web endpoint.
+pub const GOOGLE_WEB_BASE_URL: &str = "https://translate.googleapis.com/translate_a/single";
+
+/// Maximum number of characters sent in a single request by default.
+pub const GOOGLE_WEB_MAX_CHARS: usize = 5_000;
+
+/// # Google Translate web endpoint
+///
+/// A struct representing the keyless endpoint used by the Google Translate web pages and browser extensions.
+///
+/// It does not need any key, but it is not a documented API: it is rate-limited, may change without notice,
+/// and should only be used for small scripts. Use [`GoogleV2`](struct.GoogleV2.html) or
+/// [`GoogleV3`](struct.GoogleV3.html) for anything else.
+///
+/// Each request is limited to [`GOOGLE_WEB_MAX_CHARS`](constant.GOOGLE_WEB_MAX_CHARS.html) characters:
+/// longer texts are split on line, sentence or word boundaries, translated piece by piece and joined back.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Japanese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct, no key needed
+/// let translator = GoogleWeb::new();
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Japanese)
+///     .await?;
+///
+/// assert_eq!(translated_text, "こんにちは、私の名前はうずまきナルトです！");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = GoogleWeb::new();
+/// let text: String = "Bonjour, je m'appelle Naruto Uzumaki!".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::French));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct GoogleWeb<'a> {
+    base_url: &'a str,
+    max_chars: usize,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> GoogleWeb<'a> {
+    /// Sends the requests to the given URL instead of [`GOOGLE_WEB_BASE_URL`](constant.GOOGLE_WEB_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sets the maximum number of characters sent in a single request.
+    ///
+    /// Texts longer than this limit are split. A limit of `0` is treated as `1`.
+    pub const fn with_max_chars(mut self, max_chars: usize) -> Self {
+        self.max_chars = max_chars;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Sends a single request, and returns the response json body.
+    async fn request(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: &str,
+    ) -> Result<Value, Error> {
+        // build query
+        let url = format!(
+            "{}?client=gtx&sl={}&tl={}&dt=t",
+            self.base_url, source_language, target_language
+        );
+        let body = format!("q={}", encode(text));
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body).await?;
+
+        match from_str(body.as_str()) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(Error::InvalidResponse(ErrorSource::new(err))),
+        }
+    }
+}
+
+#[async_trait]
+impl<'a> Api for GoogleWeb<'a> {
+    /// Returns a new [`GoogleWeb`](struct.GoogleWeb.html) struct.
+    fn new() -> Self {
+        Self {
+            base_url: GOOGLE_WEB_BASE_URL,
+            max_chars: GOOGLE_WEB_MAX_CHARS,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                language_code(source)
+            }
+        };
+
+        let mut translation = String::with_capacity(text.len());
+        for chunk in split_text(&text, self.max_chars) {
+            // the endpoint trims the text, keep the surrounding whitespace as is
+            let content = chunk.trim();
+            let leading = &chunk[..chunk.len() - chunk.trim_start().len()];
+            let trailing = &chunk[chunk.trim_end().len()..];
+
+            translation.push_str(leading);
+            if !content.is_empty() {
+                let json_body = self
+                    .request(content, source_language, language_code(target_language))
+                    .await?;
+                translation.push_str(&get_text(&json_body).ok_or(Error::CouldNotDerializeJson)?);
+            }
+            translation.push_str(trailing);
+        }
+
+        Ok(translation)
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for GoogleWeb<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        // the first chunk is enough to detect the language
+        let chunk = match split_text(text.trim(), self.max_chars).first() {
+            Some(chunk) => chunk.trim().to_string(),
+            None => return Ok(None),
+        };
+
+        let json_body = self
+            .request(&chunk, "auto", Language::English.to_language_code())
+            .await?;
+
+        Ok(get_lang(&json_body))
+    }
+}
+
+/// Returns the code used by the endpoint for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CN",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the translated text of a response, made of the translations of each sentence.
+fn get_text(json_body: &Value) -> Option<String> {
+    json_body
+        .get(0)?
+        .as_array()?
+        .iter()
+        .map(|sentence| sentence.get(0).and_then(Value::as_str))
+        .collect::<Option<Vec<&str>>>()
+        .map(|sentences| sentences.concat())
+}
+
+/// Returns the detected language of a response.
+fn get_lang(json_body: &Value) -> Option<Language> {
+    match json_body.get(2)?.as_str()? {
+        "zh-CN" | "zh-TW" => Some(Language::Chinese),
+        "iw" => Some(Language::Hebrew),
+        code => Language::from_language_code(code),
+    }
+}
+
+/// Splits a text in chunks of at most `max_chars` characters, whose concatenation is the original text.
+///
+/// Texts are preferably split after a line break, then after the end of a sentence, then after a whitespace.
+/// Words longer than the limit are split anywhere.
+fn split_text(text: &str, max_chars: usize) -> Vec<&str> {
+    let max_chars = max_chars.max(1);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        // byte index after the `max_chars` first characters
+        let limit = match rest.char_indices().nth(max_chars) {
+            Some((index, _)) => index,
+            None => {
+                chunks.push(rest);
+                break;
+            }
+        };
+        let candidate = &rest[..limit];
+
+        // split after the separator, keeping it in the current chunk
+        let split = candidate
+            .rfind('\n')
+            .map(|index| index + 1)
+            .or_else(|| {
+                [". ", "! ", "? ", "。", "！", "？"]
+                    .iter()
+                    .filter_map(|end| candidate.rfind(end).map(|index| index + end.len()))
+                    .max()
+            })
+            .or_else(|| {
+                candidate
+                    .char_indices()
+                    .rev()
+                    .find(|(_, c)| c.is_whitespace())
+                    .map(|(index, c)| index + c.len_utf8())
+            })
+            .unwrap_or(limit);
+
+        chunks.push(&rest[..split]);
+        rest = &rest[split..];
+    }
+
+    chunks
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::GoogleWebAPIError(GoogleWebError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Enum containing different errors that may be returned by the keyless Google endpoint.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum GoogleWebError {
+    /// The request is invalid, e.g. an unsupported language.
+    BadRequest,
+    /// The text sent is too long.
+    MaxTextSizeExceeded,
+    /// Too many requests were sent, the client has been temporarily blocked.
+    TooManyRequests,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for GoogleWebError {
+    fn from_error_code(code: u16) -> Self {
+        use GoogleWebError::*;
+        match code {
+            400 => BadRequest,
+            413 => MaxTextSizeExceeded,
+            429 => TooManyRequests,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use GoogleWebError::*;
+        match self {
+            BadRequest => 400,
+            MaxTextSizeExceeded => 413,
+            TooManyRequests => 429,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use GoogleWebError::*;
+        match self {
+            TooManyRequests => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use GoogleWebError::*;
+        match self {
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use GoogleWebError::*;
+        match self {
+            TooManyRequests => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// The endpoint answers the unsupported languages with a bad request, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for GoogleWebError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Google web error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for GoogleWebError {}
@@ -0,0 +1,386 @@
+/*!
+A module containing the implementation of the [LibreTranslate API](https://libretranslate.com/docs).
+
+To use it, see the [`LibreTranslate struct`](struct.LibreTranslate.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+use super::*;
+
+/// Base URL of the public LibreTranslate instance, which needs an API key.
+pub const LIBRETRANSLATE_BASE_URL: &str = "https://libretranslate.com";
+
+/// Helper structure of the request body of a LibreTranslate translate request
+#[derive(Serialize)]
+struct TranslateRequestBody<'a> {
+    q: &'a str,
+    source: &'a str,
+    target: &'a str,
+    format: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+/// Helper structure of the request body of a LibreTranslate detect request
+#[derive(Serialize)]
+struct DetectRequestBody<'a> {
+    q: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_key: Option<&'a str>,
+}
+
+/// # LibreTranslate API
+///
+/// A struct representing the [LibreTranslate API](https://libretranslate.com/docs), which can be self-hosted.
+///
+/// The API key is optional: instances started without `--api-keys` accept anonymous requests,
+/// in which case no key needs to be set. The key is only sent when set.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+/// - optional API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+///
+/// To use it, first construct the struct with the URL of the instance, then do the desired function calls.
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Spanish, using a local instance:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct, without API key
+/// let translator = LibreTranslate::new().with_base_url("http://localhost:5000");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Spanish)
+///     .await?;
+///
+/// assert_eq!(translated_text, "¡Hola, mi nombre es Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text, using the public instance:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = LibreTranslate::with_key("<LIBRETRANSLATE_API_KEY>");
+/// let text: String = "Bonjour, je m'appelle Naruto Uzumaki!".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::French));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct LibreTranslate<'a> {
+    key: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> LibreTranslate<'a> {
+    /// Returns a new [`LibreTranslate`](struct.LibreTranslate.html) struct with the given API key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            key: Some(key),
+            base_url: LIBRETRANSLATE_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the instance at the given URL instead of [`LIBRETRANSLATE_BASE_URL`](constant.LIBRETRANSLATE_BASE_URL.html).
+    ///
+    /// The URL must not end with a slash, e.g. `http://localhost:5000`.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+}
+
+impl<'a> ApiKey<'a> for LibreTranslate<'a> {
+    fn set_set(&mut self, key: &'a str) {
+        self.key = Some(key)
+    }
+
+    fn get_key(&self) -> Option<&'a str> {
+        self.key
+    }
+
+    fn is_key_required(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl<'a> Api for LibreTranslate<'a> {
+    /// Returns a new [`LibreTranslate`](struct.LibreTranslate.html) struct without API key, using the public instance.
+    ///
+    /// To use another instance, use the [`with_base_url`](struct.LibreTranslate.html#method.with_base_url) method.
+    fn new() -> Self {
+        Self {
+            key: None,
+            base_url: LIBRETRANSLATE_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                source.to_language_code()
+            }
+        };
+
+        // build query
+        let url = format!("{}/translate", self.base_url);
+        let body = serde_json::to_string(&TranslateRequestBody {
+            q: &text,
+            source: source_language,
+            target: target_language.to_language_code(),
+            format: "text",
+            api_key: self.key,
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for LibreTranslate<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        // build query
+        let url = format!("{}/detect", self.base_url);
+        let body = serde_json::to_string(&DetectRequestBody {
+            q: &text,
+            api_key: self.key,
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body).await?;
+
+        let json_body: Vec<Detection> = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.first().and_then(|detection| detection.get_lang()))
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::LibreTranslateAPIError(
+            LibreTranslateError::from_response(status, &body),
+        )),
+    }
+}
+
+/// Serializable struct of a LibreTranslate translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    #[serde(rename = "translatedText")]
+    translated_text: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.translated_text.clone()
+    }
+}
+
+/// Language detected in a LibreTranslate detect response
+#[derive(Debug, Serialize, Deserialize)]
+struct Detection {
+    confidence: f64,
+    language: String,
+}
+
+impl ApiDetectResponse for Detection {
+    fn get_lang(&self) -> Option<Language> {
+        match self.language.as_str() {
+            "zh-Hans" | "zh-Hant" => Some(Language::Chinese),
+            "nb" => Some(Language::Norwegian),
+            code => Language::from_language_code(code),
+        }
+    }
+}
+
+/// Serializable struct of a LibreTranslate error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Enum containing different errors that may be returned by the LibreTranslate API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum LibreTranslateError {
+    /// Invalid request, e.g. an unsupported language or a missing API key.
+    BadRequest,
+    /// The API key is invalid, or the client has been banned.
+    Forbidden,
+    /// Too many requests were sent, the request should be retried later.
+    SlowDown,
+    /// The instance failed to translate the text.
+    CouldNotTranslate,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for LibreTranslateError {
+    fn from_error_code(code: u16) -> Self {
+        use LibreTranslateError::*;
+        match code {
+            400 => BadRequest,
+            403 => Forbidden,
+            429 => SlowDown,
+            500 => CouldNotTranslate,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use LibreTranslateError::*;
+        match self {
+            BadRequest => 400,
+            Forbidden => 403,
+            SlowDown => 429,
+            CouldNotTranslate => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `error` of the error response, kept as the message.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let error = ProviderError::new(Self::from_error_code(status), status);
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => error.with_message(&res.error),
+            Err(_) => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use LibreTranslateError::*;
+        match self {
+            SlowDown | CouldNotTranslate => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use LibreTranslateError::*;
+        match self {
+            Forbidden => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use LibreTranslateError::*;
+        match self {
+            SlowDown => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// LibreTranslate reports the unsupported languages as bad requests, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for LibreTranslateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "LibreTranslate error {} : {:?}",
+            self.to_error_code(),
+            self
+        )
+    }
+}
+
+impl std::error::Error for LibreTranslateError {}
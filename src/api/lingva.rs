@@ -0,0 +1,258 @@
+/*!
+A module containing the implementation of the [Lingva Translate API](https://github.com/thedaviddelta/lingva-translate#public-apis).
+
+To use it, see the [`Lingva struct`](struct.Lingva.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL of the official Lingva Translate instance.
+pub const LINGVA_BASE_URL: &str = "https://lingva.ml";
+
+/// # Lingva Translate API
+///
+/// A struct representing the API of a [Lingva Translate](https://github.com/thedaviddelta/lingva-translate) instance,
+/// a free front-end to Google Translate which does not need any key.
+///
+/// Public instances are rate-limited, and instances can be self-hosted.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Japanese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct, no key needed
+/// let translator = Lingva::new().with_base_url("https://lingva.example.com");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Japanese)
+///     .await?;
+///
+/// assert_eq!(translated_text, "こんにちは、私の名前はうずまきナルトです！");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Lingva<'a> {
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> Lingva<'a> {
+    /// Sends the requests to the instance at the given URL instead of [`LINGVA_BASE_URL`](constant.LINGVA_BASE_URL.html).
+    ///
+    /// The URL must not end with a slash, e.g. `http://localhost:3000`.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+}
+
+#[async_trait]
+impl<'a> Api for Lingva<'a> {
+    /// Returns a new [`Lingva`](struct.Lingva.html) struct, using the official instance.
+    fn new() -> Self {
+        Self {
+            base_url: LINGVA_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                source.to_language_code()
+            }
+        };
+
+        // build query, the text is a part of the path
+        let url = format!(
+            "{}/api/v1/{}/{}/{}",
+            self.base_url,
+            source_language,
+            target_language.to_language_code(),
+            encode(&text)
+        );
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(client: Option<&HttpClient>, uri: Uri) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::LingvaAPIError(LingvaError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a Lingva translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    translation: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.translation.clone()
+    }
+}
+
+/// Serializable struct of a Lingva error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    error: String,
+}
+
+/// Enum containing different errors that may be returned by a Lingva Translate instance.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum LingvaError {
+    /// Invalid request, e.g. an unsupported language.
+    BadRequest,
+    /// The API is not available at this URL, e.g. an instance without API.
+    NotFound,
+    /// Too many requests were sent, the request should be retried later.
+    TooManyRequests,
+    /// The instance failed to get the translation from Google Translate.
+    TranslationFailed,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for LingvaError {
+    fn from_error_code(code: u16) -> Self {
+        use LingvaError::*;
+        match code {
+            400 => BadRequest,
+            404 => NotFound,
+            429 => TooManyRequests,
+            500 => TranslationFailed,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use LingvaError::*;
+        match self {
+            BadRequest => 400,
+            NotFound => 404,
+            TooManyRequests => 429,
+            TranslationFailed => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `error` of the error response, kept as the message.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let error = ProviderError::new(Self::from_error_code(status), status);
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => error.with_message(&res.error),
+            Err(_) => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use LingvaError::*;
+        match self {
+            TooManyRequests | TranslationFailed => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use LingvaError::*;
+        match self {
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use LingvaError::*;
+        match self {
+            TooManyRequests => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// Lingva reports the unsupported languages as bad requests, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for LingvaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Lingva error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for LingvaError {}
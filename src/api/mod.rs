@@ -1,12 +1,85 @@
 use crate::*;
 use async_trait::async_trait;
 
+pub mod client;
+pub use client::HttpClient;
+
+pub mod transport;
+pub use transport::{HyperTransport, MemoryRequest, MemoryTransport, Transport};
+
+pub mod retry;
+pub use retry::RetryPolicy;
+
+pub mod timeout;
+pub use timeout::Timeouts;
+
+pub mod proxy;
+pub use proxy::Proxy;
+
+mod tls;
+
+pub mod rate_limit;
+pub use rate_limit::RateLimited;
+
 pub mod yandex;
+#[allow(deprecated)]
 pub use yandex::Yandex;
 
+pub mod yandex_cloud;
+pub use yandex_cloud::YandexCloud;
+
 pub mod google_v2;
 pub use google_v2::GoogleV2;
 
+pub mod google_v3;
+pub use google_v3::GoogleV3;
+
+#[cfg(feature = "keyless")]
+pub mod google_web;
+#[cfg(feature = "keyless")]
+pub use google_web::GoogleWeb;
+
+pub mod deepl;
+pub use deepl::DeepL;
+
+pub mod azure;
+pub use azure::AzureTranslator;
+
+pub mod libretranslate;
+pub use libretranslate::LibreTranslate;
+
+pub mod amazon;
+pub use amazon::AmazonTranslate;
+
+pub mod mymemory;
+pub use mymemory::MyMemory;
+
+pub mod lingva;
+pub use lingva::Lingva;
+
+pub mod apertium;
+pub use apertium::Apertium;
+
+pub mod papago;
+pub use papago::Papago;
+
+pub mod baidu;
+pub use baidu::Baidu;
+
+pub mod youdao;
+pub use youdao::Youdao;
+
+pub mod modernmt;
+pub use modernmt::ModernMt;
+
+pub mod llm;
+pub use llm::LlmTranslator;
+
+#[cfg(feature = "offline")]
+pub mod offline;
+#[cfg(feature = "offline")]
+pub use offline::Offline;
+
 /// A trait defining a translate API.
 ///
 /// Implements `new()` to return a new API, and `translate()` to translate a text.
@@ -29,6 +102,69 @@ pub trait Api {
         source_language: InputLanguage,
         target_language: Language,
     ) -> Result<String, Error>;
+
+    /// Translates several texts between two languages.
+    ///
+    /// Returns the translations in the same order as `texts`. If some texts could not be translated,
+    /// returns an [`Error::BatchError`](../enum.Error.html#variant.BatchError) holding the result of each text,
+    /// so that the other translations are not lost. Errors detected before sending any request,
+    /// e.g. a missing API key, may be returned as is.
+    ///
+    /// The default implementation translates the texts one by one. Engines able to translate several texts
+    /// in a single request override it, e.g. [`GoogleV2`](struct.GoogleV2.html).
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<Vec<String>, Error> {
+        let mut results = Vec::with_capacity(texts.len());
+        for text in texts {
+            results.push(self.translate(text, source_language, target_language).await);
+        }
+
+        batch_results(results)
+    }
+
+    /// Returns the maximum number of texts and of characters that [`translate_batch`](#method.translate_batch)
+    /// sends in a single request, or `None` if it sends a request per text, like the default implementation.
+    ///
+    /// Engines overriding `translate_batch` to send several texts per request override it too, so that
+    /// a [`RateLimited`](struct.RateLimited.html) engine counts their requests.
+    fn batch_limits(&self) -> Option<(usize, usize)> {
+        None
+    }
+}
+
+/// Returns the translations of a batch, or an [`Error::BatchError`](../enum.Error.html#variant.BatchError)
+/// if one of them failed.
+fn batch_results(results: Vec<Result<String, Error>>) -> Result<Vec<String>, Error> {
+    if results.iter().all(Result::is_ok) {
+        Ok(results.into_iter().filter_map(Result::ok).collect())
+    } else {
+        Err(Error::BatchError(results))
+    }
+}
+
+/// Splits the texts of a batch into chunks of at most `max_texts` texts and `max_characters` characters,
+/// sent in a single request each. A text longer than `max_characters` is sent alone.
+fn batch_chunks(texts: &[String], max_texts: usize, max_characters: usize) -> Vec<&[String]> {
+    let mut chunks = Vec::new();
+    let (mut start, mut characters) = (0, 0);
+    for (i, text) in texts.iter().enumerate() {
+        let length = text.chars().count();
+        if i > start && (i - start == max_texts || characters + length > max_characters) {
+            chunks.push(&texts[start..i]);
+            start = i;
+            characters = 0;
+        }
+        characters += length;
+    }
+    if start < texts.len() {
+        chunks.push(&texts[start..]);
+    }
+
+    chunks
 }
 
 /// Extends [`Api`](trait.Api.html) to implement language detection.
@@ -55,6 +191,14 @@ pub trait ApiKey<'a>: Api + Sized {
     fn set_set(&mut self, key: &'a str);
 
     fn get_key(&self) -> Option<&'a str>;
+
+    /// Returns `true` if the API cannot be used without a key, which is the default.
+    ///
+    /// APIs accepting anonymous requests, such as self-hosted instances, return `false`:
+    /// their key is only sent when it is set.
+    fn is_key_required(&self) -> bool {
+        true
+    }
 }
 
 trait ApiTranslateResponse {
@@ -66,10 +210,162 @@ trait ApiDetectResponse {
 }
 
 /// Used on enums representing errors that a call to an API returned.
-pub trait ApiError {
+pub trait ApiError: Sized {
     /// Converts an error code to the enum variant.
     fn from_error_code(code: u16) -> Self;
 
     /// Converts an error variant to the matching error code.
     fn to_error_code(&self) -> u16;
+
+    /// Parses an error response of the API, from its HTTP status code and its body.
+    ///
+    /// The engines parse the error payload documented by their API, keeping the code and the message
+    /// of the provider. The default implementation only looks at the status code.
+    fn from_response(status: u16, _body: &str) -> ProviderError<Self> {
+        ProviderError::new(Self::from_error_code(status), status)
+    }
+
+    /// Returns whether the error is transient, the same request being likely to succeed later,
+    /// e.g. a rate limiting or a temporarily unavailable server.
+    ///
+    /// The requests failing with a retryable error are retried by a [`RetryPolicy`](struct.RetryPolicy.html),
+    /// even when the API reports it in a successful response.
+    fn is_retryable(&self) -> bool;
+
+    /// Returns whether the credentials are missing, invalid, or not allowed to use the API.
+    fn is_auth(&self) -> bool;
+
+    /// Returns whether a usage limit of the account has been reached: either a rate limit, also retryable,
+    /// or a quota, which is not until it is reset or raised.
+    fn is_quota(&self) -> bool;
+
+    /// Returns whether the pair of languages, or one of them, is not supported by the API.
+    fn is_unsupported_pair(&self) -> bool;
+}
+
+/// Returns whether an error of the given HTTP status code is transient, for the error codes unknown to an engine.
+fn is_retryable_status(status: u16) -> bool {
+    retry::DEFAULT_RETRYABLE_STATUSES.contains(&status)
 }
+
+/// Returns whether an error of the given HTTP status code is an authentication failure, for the error codes
+/// unknown to an engine.
+fn is_auth_status(status: u16) -> bool {
+    status == 401 || status == 403
+}
+
+/// Returns whether an error of the given HTTP status code is a rate limiting, for the error codes unknown to an engine.
+fn is_quota_status(status: u16) -> bool {
+    status == 429
+}
+
+/// # Provider error
+///
+/// An error returned by an API: the variant of the error enum of the engine, along with the HTTP status
+/// code of the response, and the error code and message of the provider when the response carries them.
+///
+/// Errors detected before sending a request convert to it with `From`, using the status code matching the variant.
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
+pub struct ProviderError<E> {
+    kind: E,
+    status: u16,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl<E> ProviderError<E> {
+    /// Returns a new [`ProviderError`](struct.ProviderError.html) of the given kind and HTTP status code,
+    /// without provider code nor message.
+    pub fn new(kind: E, status: u16) -> Self {
+        Self {
+            kind,
+            status,
+            code: None,
+            message: None,
+        }
+    }
+
+    /// Sets the error code of the provider, e.g. `keyInvalid` or `52003`.
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        self
+    }
+
+    /// Sets the error message of the provider.
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    /// Returns the variant of the error enum of the engine.
+    pub fn get_kind(&self) -> &E {
+        &self.kind
+    }
+
+    /// Returns the HTTP status code of the response.
+    ///
+    /// Some APIs report their errors in responses with a `200` status code.
+    pub fn get_status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns the error code of the provider, if the response had one.
+    pub fn get_code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    /// Returns the error message of the provider, if the response had one.
+    pub fn get_message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+}
+
+impl<E: ApiError> ProviderError<E> {
+    /// Returns whether the error is transient, see [`ApiError::is_retryable`](trait.ApiError.html#tymethod.is_retryable).
+    pub fn is_retryable(&self) -> bool {
+        self.kind.is_retryable()
+    }
+
+    /// Returns whether the credentials are rejected, see [`ApiError::is_auth`](trait.ApiError.html#tymethod.is_auth).
+    pub fn is_auth(&self) -> bool {
+        self.kind.is_auth()
+    }
+
+    /// Returns whether a usage limit has been reached, see [`ApiError::is_quota`](trait.ApiError.html#tymethod.is_quota).
+    pub fn is_quota(&self) -> bool {
+        self.kind.is_quota()
+    }
+
+    /// Returns whether the languages are not supported,
+    /// see [`ApiError::is_unsupported_pair`](trait.ApiError.html#tymethod.is_unsupported_pair).
+    pub fn is_unsupported_pair(&self) -> bool {
+        self.kind.is_unsupported_pair()
+    }
+}
+
+impl<E: ApiError> From<E> for ProviderError<E> {
+    fn from(kind: E) -> Self {
+        let status = kind.to_error_code();
+        Self::new(kind, status)
+    }
+}
+
+impl<E: ApiError + std::fmt::Display> std::fmt::Display for ProviderError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.kind)?;
+        // the kind is displayed with its own code already
+        if let Some(code) = self
+            .code
+            .as_ref()
+            .filter(|code| **code != self.kind.to_error_code().to_string())
+        {
+            write!(f, " ({})", code)?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+        Ok(())
+    }
+}
+
+impl<E: ApiError + std::fmt::Debug + std::fmt::Display> std::error::Error for ProviderError<E> {}
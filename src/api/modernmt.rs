@@ -0,0 +1,620 @@
+/*!
+A module containing the implementation of the [ModernMT API](https://www.modernmt.com/api/).
+
+To use it, see the [`ModernMt struct`](struct.ModernMt.html).
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the ModernMT API.
+pub const MODERNMT_BASE_URL: &str = "https://api.modernmt.com";
+
+/// # ModernMT API
+///
+/// A struct representing the [ModernMT API](https://www.modernmt.com/api/), an adaptive translation engine.
+///
+/// ModernMT adapts its translations to the translation memories of the account. The memories to use are
+/// weighted by a context vector, e.g. `1:0.75,2:0.25`, which can be set with
+/// [`with_context_vector`](struct.ModernMt.html#method.with_context_vector) or computed from a sample text with
+/// [`compute_context_vector`](struct.ModernMt.html#method.compute_context_vector). Memories can also be given as hints,
+/// with [`with_hints`](struct.ModernMt.html#method.with_hints).
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+/// - API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+/// - memory management, with [`create_memory`](struct.ModernMt.html#method.create_memory),
+///   [`add_to_memory`](struct.ModernMt.html#method.add_to_memory) and [`import_tmx`](struct.ModernMt.html#method.import_tmx)
+///
+/// ### Text translation
+///
+/// Translate a text from English to Italian, adapted to the memory `1`:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = ModernMt::with_key("<MODERNMT_API_KEY>").with_context_vector("1:1");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Defined(Language::English), Language::Italian)
+///     .await?;
+///
+/// assert_eq!(translated_text, "Ciao, mi chiamo Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Memory management
+///
+/// Create a memory and add a translation unit to it:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = ModernMt::with_key("<MODERNMT_API_KEY>");
+///
+/// let memory = translator.create_memory("Naruto", None).await?;
+///
+/// // the units are imported in the background, see the returned job
+/// let job = translator
+///     .add_to_memory(
+///         memory.id,
+///         Language::English,
+///         Language::Italian,
+///         "Believe it!",
+///         "Puoi contarci!",
+///     )
+///     .await?;
+///
+/// assert_eq!(job.memory, memory.id);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct ModernMt<'a> {
+    key: Option<&'a str>,
+    base_url: &'a str,
+    context_vector: Option<&'a str>,
+    hints: &'a [u64],
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> ModernMt<'a> {
+    /// Returns a new [`ModernMt`](struct.ModernMt.html) struct with the given API key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            key: Some(key),
+            base_url: MODERNMT_BASE_URL,
+            context_vector: None,
+            hints: &[],
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`MODERNMT_BASE_URL`](constant.MODERNMT_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Adapts the translations to the memories of the given context vector, e.g. `1:0.75,2:0.25`.
+    pub const fn with_context_vector(mut self, context_vector: &'a str) -> Self {
+        self.context_vector = Some(context_vector);
+        self
+    }
+
+    /// Gives priority to the memories with the given IDs when adapting the translations.
+    pub const fn with_hints(mut self, hints: &'a [u64]) -> Self {
+        self.hints = hints;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the context vector sent with the translations, if set.
+    pub fn get_context_vector(&self) -> Option<&'a str> {
+        self.context_vector
+    }
+
+    /// Computes the context vector of a sample text, matching it against the memories of the account.
+    ///
+    /// Returns `None` if no memory matches the text.
+    pub async fn compute_context_vector(
+        &self,
+        text: &str,
+        source_language: Language,
+        target_language: Language,
+    ) -> Result<Option<String>, Error> {
+        let target_code = language_code(target_language);
+        let path = format!(
+            "/context-vector?source={}&targets={}&text={}",
+            language_code(source_language),
+            target_code,
+            encode(text)
+        );
+
+        let response: ContextVectorResponse = self.request("GET", &path, None).await?;
+
+        Ok(response.vectors.get(target_code).cloned())
+    }
+
+    /// Creates a new memory, returning it with its ID.
+    pub async fn create_memory(
+        &self,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<ModernMtMemory, Error> {
+        let body = serde_json::to_vec(&CreateMemoryRequest { name, description })
+            .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        self.request("POST", "/memories", Some(("application/json", body)))
+            .await
+    }
+
+    /// Adds a translation unit to a memory.
+    ///
+    /// The unit is imported in the background, the returned [`ModernMtImportJob`](struct.ModernMtImportJob.html)
+    /// describes its progress.
+    pub async fn add_to_memory(
+        &self,
+        memory_id: u64,
+        source_language: Language,
+        target_language: Language,
+        sentence: &str,
+        translation: &str,
+    ) -> Result<ModernMtImportJob, Error> {
+        let body = serde_json::to_vec(&AddToMemoryRequest {
+            source: language_code(source_language),
+            target: language_code(target_language),
+            sentence,
+            translation,
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        self.request(
+            "POST",
+            &format!("/memories/{}/content", memory_id),
+            Some(("application/json", body)),
+        )
+        .await
+    }
+
+    /// Imports the translation units of a [TMX](https://en.wikipedia.org/wiki/Translation_Memory_eXchange) file into a memory.
+    ///
+    /// The units are imported in the background, the returned [`ModernMtImportJob`](struct.ModernMtImportJob.html)
+    /// describes its progress.
+    pub async fn import_tmx(&self, memory_id: u64, tmx: &[u8]) -> Result<ModernMtImportJob, Error> {
+        // the boundary only has to be absent from the file
+        let boundary = format!(
+            "text-translator-{}",
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_nanos()
+        );
+
+        let mut body = format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"tmx\"; filename=\"memory.tmx\"\r\nContent-Type: application/x-tmx+xml\r\n\r\n",
+            boundary
+        )
+        .into_bytes();
+        body.extend_from_slice(tmx);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+        let content_type = format!("multipart/form-data; boundary={}", boundary);
+
+        self.request(
+            "POST",
+            &format!("/memories/{}/content", memory_id),
+            Some((&content_type, body)),
+        )
+        .await
+    }
+
+    /// Sends a request to the API, returning the `data` field of the response.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<(&str, Vec<u8>)>,
+    ) -> Result<T, Error> {
+        let key = self.key.ok_or(Error::NoApiKeySet)?;
+
+        let url = format!("{}{}", self.base_url, path);
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, method, uri, key, body).await?;
+
+        let json_body: Response<T> = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.data)
+    }
+}
+
+impl<'a> ApiKey<'a> for ModernMt<'a> {
+    fn set_set(&mut self, key: &'a str) {
+        self.key = Some(key)
+    }
+
+    fn get_key(&self) -> Option<&'a str> {
+        self.key
+    }
+}
+
+#[async_trait]
+impl<'a> Api for ModernMt<'a> {
+    /// Returns a new [`ModernMt`](struct.ModernMt.html) struct without API key.
+    ///
+    /// To set it, use [`with_key`](struct.ModernMt.html#method.with_key) or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
+    fn new() -> Self {
+        Self {
+            key: None,
+            base_url: MODERNMT_BASE_URL,
+            context_vector: None,
+            hints: &[],
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // build query
+        let mut path = format!(
+            "/translate?target={}&q={}",
+            language_code(target_language),
+            encode(&text)
+        );
+
+        // get translation direction, the source is detected when it is not set
+        if let InputLanguage::Defined(source) = source_language {
+            // verify that source languages != target language
+            if source == target_language {
+                return Err(Error::SameLanguages(source, target_language));
+            }
+
+            path.push_str(&format!("&source={}", language_code(source)));
+        }
+
+        if let Some(context_vector) = self.context_vector {
+            path.push_str(&format!("&context_vector={}", encode(context_vector)));
+        }
+
+        if !self.hints.is_empty() {
+            let hints = self
+                .hints
+                .iter()
+                .map(|hint| hint.to_string())
+                .collect::<Vec<String>>()
+                .join(",");
+            path.push_str(&format!("&hints={}", encode(&hints)));
+        }
+
+        let json_body: Translation = self.request("GET", &path, None).await?;
+
+        Ok(json_body.get_text())
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for ModernMt<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        let path = format!("/translate/detect?q={}", encode(&text));
+
+        let json_body: Detection = self.request("GET", &path, None).await?;
+
+        Ok(json_body.get_lang())
+    }
+}
+
+/// Returns the code used by ModernMT for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CN",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    method: &str,
+    uri: Uri,
+    key: &str,
+    body: Option<(&str, Vec<u8>)>,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("MMT-ApiKey", key)
+        .header("MMT-Platform", "text-translator");
+    let req = match body {
+        Some((content_type, body)) => req
+            .header("Content-Type", content_type)
+            .body(Body::from(body)),
+        None => req.body(Body::empty()),
+    }
+    .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::ModernMtAPIError(ModernMtError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// A translation memory of a ModernMT account.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModernMtMemory {
+    /// ID of the memory, used in context vectors and hints.
+    pub id: u64,
+    /// Name of the memory.
+    pub name: String,
+    /// Description of the memory, if set.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Date of creation of the memory, e.g. `2024-01-31T10:00:00+00:00`.
+    #[serde(default)]
+    pub creation_date: Option<String>,
+}
+
+/// A background job importing translation units into a memory.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ModernMtImportJob {
+    /// ID of the job.
+    pub id: String,
+    /// ID of the memory the units are imported into.
+    pub memory: u64,
+    /// Number of translation units imported by the job.
+    #[serde(default)]
+    pub size: u64,
+    /// Progress of the job, from `0.0` to `1.0`.
+    #[serde(default)]
+    pub progress: f64,
+}
+
+/// Serializable struct of a ModernMT successful response
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    data: T,
+}
+
+/// Data of a ModernMT translate response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Translation {
+    translation: String,
+}
+
+impl ApiTranslateResponse for Translation {
+    fn get_text(&self) -> String {
+        self.translation.clone()
+    }
+}
+
+/// Data of a ModernMT detect response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Detection {
+    detected_language: String,
+}
+
+impl ApiDetectResponse for Detection {
+    fn get_lang(&self) -> Option<Language> {
+        // regional variants, e.g. `zh-CN`, are returned as their language
+        self.detected_language
+            .split('-')
+            .next()
+            .and_then(Language::from_language_code)
+    }
+}
+
+/// Data of a ModernMT context-vector response
+#[derive(Debug, Deserialize)]
+struct ContextVectorResponse {
+    #[serde(default)]
+    vectors: std::collections::HashMap<String, String>,
+}
+
+/// Serializable body of a create memory request
+#[derive(Serialize)]
+struct CreateMemoryRequest<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+}
+
+/// Serializable body of a request adding a translation unit to a memory
+#[derive(Serialize)]
+struct AddToMemoryRequest<'a> {
+    source: &'a str,
+    target: &'a str,
+    sentence: &'a str,
+    translation: &'a str,
+}
+
+/// Serializable struct of a ModernMT error response
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorDetails,
+}
+
+/// Details of a ModernMT error response
+#[derive(Debug, Deserialize)]
+struct ErrorDetails {
+    #[serde(rename = "type")]
+    error_type: String,
+    message: Option<String>,
+}
+
+/// Enum containing different errors that may be returned by the ModernMT API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum ModernMtError {
+    /// Invalid request, e.g. a missing parameter or a malformed context vector.
+    BadRequest,
+    /// The language, or the pair of languages, is not supported.
+    UnsupportedLanguage,
+    /// The API key is invalid.
+    InvalidApiKey,
+    /// The account has no active plan, or its quota has been exceeded.
+    PaymentRequired,
+    /// The API key is not allowed to do this request.
+    Forbidden,
+    /// The memory does not exist.
+    NotFound,
+    /// Too many requests were sent, the request should be retried later.
+    TooManyRequests,
+    /// An internal error happened.
+    ServerError,
+    /// The service is temporarily unavailable.
+    ServiceUnavailable,
+    UnknownErrorCode(u16),
+}
+
+impl ModernMtError {
+    /// Converts the `type` of an error response, e.g. `UnsupportedLanguageException`, to the enum variant.
+    ///
+    /// Returns `None` if the type has no matching variant.
+    pub fn from_error_type(error_type: &str) -> Option<Self> {
+        use ModernMtError::*;
+        match error_type {
+            "ValidationException" | "IllegalArgumentException" => Some(BadRequest),
+            "UnsupportedLanguageException" => Some(UnsupportedLanguage),
+            "AuthenticationException" => Some(InvalidApiKey),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for ModernMtError {
+    fn from_error_code(code: u16) -> Self {
+        use ModernMtError::*;
+        match code {
+            400 => BadRequest,
+            401 => InvalidApiKey,
+            402 => PaymentRequired,
+            403 => Forbidden,
+            404 => NotFound,
+            429 => TooManyRequests,
+            500 => ServerError,
+            503 => ServiceUnavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use ModernMtError::*;
+        match self {
+            BadRequest | UnsupportedLanguage => 400,
+            InvalidApiKey => 401,
+            PaymentRequired => 402,
+            Forbidden => 403,
+            NotFound => 404,
+            TooManyRequests => 429,
+            ServerError => 500,
+            ServiceUnavailable => 503,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `type` and the `message` of the error response.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let res = match from_str::<ErrorResponse>(body) {
+            Ok(res) => res.error,
+            Err(_) => return ProviderError::new(Self::from_error_code(status), status),
+        };
+
+        let kind =
+            Self::from_error_type(&res.error_type).unwrap_or_else(|| Self::from_error_code(status));
+        let error = ProviderError::new(kind, status).with_code(&res.error_type);
+        match res.message {
+            Some(message) => error.with_message(&message),
+            None => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use ModernMtError::*;
+        match self {
+            TooManyRequests | ServerError | ServiceUnavailable => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use ModernMtError::*;
+        match self {
+            InvalidApiKey | Forbidden => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use ModernMtError::*;
+        match self {
+            PaymentRequired | TooManyRequests => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use ModernMtError::*;
+        matches!(self, UnsupportedLanguage)
+    }
+}
+
+impl std::fmt::Display for ModernMtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ModernMT error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for ModernMtError {}
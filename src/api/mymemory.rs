@@ -0,0 +1,485 @@
+/*!
+A module containing the implementation of the [MyMemory API](https://mymemory.translated.net/doc/spec.php).
+
+To use it, see the [`MyMemory struct`](struct.MyMemory.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::{from_str, Value};
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the MyMemory API.
+pub const MYMEMORY_BASE_URL: &str = "https://api.mymemory.translated.net";
+
+/// # MyMemory API
+///
+/// A struct representing the [MyMemory API](https://mymemory.translated.net/doc/spec.php), a free translation memory
+/// completed by machine translation.
+///
+/// It can be used anonymously with a small daily quota, which is raised by giving a valid email address.
+/// The key of a private translation memory can also be set.
+///
+/// Besides the translated text, the API returns how well the text matched the translation memory:
+/// use [`translate_with_matches`](struct.MyMemory.html#method.translate_with_matches) to get those scores.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - optional API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Italian:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct, the email address raises the daily quota
+/// let translator = MyMemory::new().with_email("naruto@konoha.jp");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Italian)
+///     .await?;
+///
+/// assert_eq!(translated_text, "Ciao, mi chiamo Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Match quality
+///
+/// Get the match quality scores of a translation:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = MyMemory::new();
+///
+/// let translation = translator
+///     .translate_with_matches(
+///         "Hello World!".to_string(),
+///         InputLanguage::Defined(Language::English),
+///         Language::Italian,
+///     )
+///     .await?;
+///
+/// println!("{} (match: {})", translation.text, translation.match_quality);
+/// for entry in translation.matches {
+///     println!("{} -> {} ({})", entry.segment, entry.translation, entry.match_quality);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct MyMemory<'a> {
+    key: Option<&'a str>,
+    email: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> MyMemory<'a> {
+    /// Returns a new [`MyMemory`](struct.MyMemory.html) struct with the key of a private translation memory.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            key: Some(key),
+            email: None,
+            base_url: MYMEMORY_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the given email address with the requests, which raises the daily quota.
+    pub const fn with_email(mut self, email: &'a str) -> Self {
+        self.email = Some(email);
+        self
+    }
+
+    /// Sends the requests to the given URL instead of [`MYMEMORY_BASE_URL`](constant.MYMEMORY_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Translates text between two languages, like [`translate`](../trait.Api.html#tymethod.translate),
+    /// and returns the match quality scores along with the translated text.
+    pub async fn translate_with_matches(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<MyMemoryTranslation, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "Autodetect",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                language_code(source)
+            }
+        };
+
+        retry::run(self.client, || {
+            self.send_translate(&text, source_language, target_language)
+        })
+        .await
+    }
+
+    /// Sends a translation request, from a source language code which may be `Autodetect`.
+    async fn send_translate(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: Language,
+    ) -> Result<MyMemoryTranslation, Error> {
+        // build query
+        let mut url = format!(
+            "{}/get?q={}&langpair={}%7C{}",
+            self.base_url,
+            encode(text),
+            source_language,
+            language_code(target_language)
+        );
+        if let Some(email) = self.email {
+            url.push_str(&format!("&de={}", encode(email)));
+        }
+        if let Some(key) = self.key {
+            url.push_str(&format!("&key={}", encode(key)));
+        }
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri).await?;
+
+        // errors may be returned with a successful HTTP status, and without the other fields
+        let status: StatusResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+        match number(&status.response_status).map(|status| status as u16) {
+            Some(200) => (),
+            Some(_) => {
+                return Err(Error::MyMemoryAPIError(MyMemoryError::from_response(
+                    200, &body,
+                )))
+            }
+            None => return Err(Error::CouldNotDerializeJson),
+        }
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.into_translation())
+    }
+}
+
+impl<'a> ApiKey<'a> for MyMemory<'a> {
+    fn set_set(&mut self, key: &'a str) {
+        self.key = Some(key)
+    }
+
+    fn get_key(&self) -> Option<&'a str> {
+        self.key
+    }
+
+    fn is_key_required(&self) -> bool {
+        false
+    }
+}
+
+#[async_trait]
+impl<'a> Api for MyMemory<'a> {
+    /// Returns a new [`MyMemory`](struct.MyMemory.html) struct, for anonymous requests.
+    fn new() -> Self {
+        Self {
+            key: None,
+            email: None,
+            base_url: MYMEMORY_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        Ok(self
+            .translate_with_matches(text, source_language, target_language)
+            .await?
+            .text)
+    }
+}
+
+/// Returns the code used by the API for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CN",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns a number which may be sent as a string, e.g. `"74"`.
+fn number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(number) => number.as_f64(),
+        Value::String(string) => string.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(client: Option<&HttpClient>, uri: Uri) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("GET")
+        .uri(uri)
+        .body(Body::empty())
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::MyMemoryAPIError(MyMemoryError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// A translation returned by MyMemory, with its match quality scores.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MyMemoryTranslation {
+    /// The translated text.
+    pub text: String,
+    /// How well the text matched the translation memory, from `0.0` to `1.0`.
+    ///
+    /// Machine translations usually have a score of `0.85`.
+    pub match_quality: f64,
+    /// The entries of the translation memory matching the text, best first.
+    pub matches: Vec<MyMemoryMatch>,
+}
+
+/// An entry of the translation memory matching a translated text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MyMemoryMatch {
+    /// The source text of the entry.
+    pub segment: String,
+    /// The translation of the entry.
+    pub translation: String,
+    /// How well the entry matches the text, from `0.0` to `1.0`.
+    pub match_quality: f64,
+    /// Quality of the translation of the entry, from `0` to `100`, if rated.
+    pub quality: Option<u8>,
+    /// Author of the entry, e.g. `MT!` for machine translations.
+    pub created_by: Option<String>,
+}
+
+/// Status of a MyMemory response, which may be an error
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StatusResponse {
+    response_status: Value,
+    #[serde(default)]
+    response_details: Value,
+    #[serde(default)]
+    response_data: Value,
+}
+
+/// Serializable struct of a MyMemory translate response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    response_data: ResponseData,
+    #[serde(default)]
+    matches: Vec<Match>,
+}
+
+impl TranslateResponse {
+    fn into_translation(self) -> MyMemoryTranslation {
+        MyMemoryTranslation {
+            text: self.get_text(),
+            match_quality: number(&self.response_data.match_quality).unwrap_or(0.0),
+            matches: self
+                .matches
+                .into_iter()
+                .map(|entry| MyMemoryMatch {
+                    match_quality: number(&entry.match_quality).unwrap_or(0.0),
+                    quality: number(&entry.quality).map(|quality| quality as u8),
+                    segment: entry.segment,
+                    translation: entry.translation,
+                    created_by: entry.created_by,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.response_data.translated_text.clone()
+    }
+}
+
+/// Best translation in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ResponseData {
+    translated_text: String,
+    #[serde(rename = "match", default)]
+    match_quality: Value,
+}
+
+/// Translation memory entry in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct Match {
+    segment: String,
+    translation: String,
+    #[serde(default)]
+    quality: Value,
+    #[serde(rename = "match", default)]
+    match_quality: Value,
+    #[serde(rename = "created-by", default)]
+    created_by: Option<String>,
+}
+
+/// Enum containing different errors that may be returned by the MyMemory API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum MyMemoryError {
+    /// Invalid request, e.g. a missing parameter.
+    BadRequest,
+    /// Invalid parameter, e.g. an unsupported language pair, an invalid email address, or a text over 500 bytes.
+    InvalidParameter,
+    /// The daily quota has been exceeded.
+    QuotaExceeded,
+    /// The service failed to process the request.
+    ServerError,
+    UnknownErrorCode(u16),
+}
+
+impl ApiError for MyMemoryError {
+    fn from_error_code(code: u16) -> Self {
+        use MyMemoryError::*;
+        match code {
+            400 => BadRequest,
+            403 => InvalidParameter,
+            429 => QuotaExceeded,
+            500 => ServerError,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use MyMemoryError::*;
+        match self {
+            BadRequest => 400,
+            InvalidParameter => 403,
+            QuotaExceeded => 429,
+            ServerError => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `responseStatus` and the `responseDetails` of the response, which may have a `200` status code.
+    ///
+    /// The status found in the body is kept as the provider code, and selects the variant. Without details,
+    /// the message is the one returned in place of the translated text.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let res = match from_str::<StatusResponse>(body) {
+            Ok(res) => res,
+            Err(_) => return ProviderError::new(Self::from_error_code(status), status),
+        };
+
+        let error = match number(&res.response_status) {
+            Some(code) => ProviderError::new(Self::from_error_code(code as u16), status)
+                .with_code(&code.to_string()),
+            None => ProviderError::new(Self::from_error_code(status), status),
+        };
+        let message = match &res.response_details {
+            Value::String(details) if !details.is_empty() => Some(details.as_str()),
+            _ => res
+                .response_data
+                .get("translatedText")
+                .and_then(Value::as_str),
+        };
+        match message {
+            Some(message) => error.with_message(message),
+            None => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use MyMemoryError::*;
+        match self {
+            ServerError => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use MyMemoryError::*;
+        match self {
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use MyMemoryError::*;
+        match self {
+            QuotaExceeded => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// MyMemory reports the unsupported pairs as invalid parameters, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for MyMemoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MyMemory error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for MyMemoryError {}
@@ -0,0 +1,560 @@
+/*!
+A module containing an offline engine, using [Argos Translate](https://github.com/argosopentech/argos-translate) packages.
+
+This module is only available with the `offline` cargo feature.
+
+To use it, see the [`Offline struct`](struct.Offline.html).
+*/
+
+use ct2rs::{Config, TranslationOptions, Translator};
+use sentencepiece::SentencePieceProcessor;
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard,
+    },
+};
+use tokio::task::spawn_blocking;
+use zip::ZipArchive;
+
+use super::*;
+
+/// Extension of the Argos Translate package archives.
+pub const ARGOS_PACKAGE_EXTENSION: &str = "argosmodel";
+
+/// Runs the translation models of Argos Translate packages.
+///
+/// Argos Translate packages contain a [CTranslate2](https://github.com/OpenNMT/CTranslate2) model in their `model`
+/// directory, and a [SentencePiece](https://github.com/google/sentencepiece) tokenizer in `sentencepiece.model`.
+/// They are run by the built-in [`CTranslate2`](struct.CTranslate2.html) backend by default: implement this trait
+/// to run them differently, and give it to the engine with [`Offline::with_backend`](struct.Offline.html#method.with_backend).
+///
+/// Backends are called synchronously, on a thread of the blocking pool of tokio, so that running a model
+/// does not stall the other tasks.
+pub trait OfflineBackend: Send + Sync {
+    /// Translates a text with the model of the given package, from its source language to its target language.
+    fn translate(&self, package: &ArgosPackage, text: &str) -> Result<String, Error>;
+}
+
+/// Serializable struct of the `metadata.json` file of an Argos Translate package
+#[derive(Debug, Serialize, Deserialize)]
+struct PackageMetadata {
+    from_code: String,
+    to_code: String,
+    package_version: Option<String>,
+}
+
+/// An Argos Translate package, translating from a language to another.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ArgosPackage {
+    /// Source language of the model.
+    pub from: Language,
+    /// Target language of the model.
+    pub to: Language,
+    /// Version of the package, if given in its metadata.
+    pub package_version: Option<String>,
+    /// Path of the package: either an `.argosmodel` archive, or an extracted package directory.
+    pub path: PathBuf,
+    /// Directory containing the package files inside the archive, e.g. `translate-en_fr-1_0`.
+    ///
+    /// Empty for extracted packages.
+    pub archive_dir: String,
+}
+
+impl ArgosPackage {
+    /// Loads the metadata of a package, from an `.argosmodel` archive or an extracted package directory.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let (metadata, archive_dir) = if path.is_dir() {
+            let metadata = std::fs::read_to_string(path.join("metadata.json"))
+                .map_err(|e| invalid_package(path, e))?;
+            (metadata, String::new())
+        } else {
+            read_archive_metadata(path)?
+        };
+
+        let metadata: PackageMetadata = match from_str(&metadata) {
+            Ok(res) => res,
+            Err(e) => return Err(invalid_package(path, e)),
+        };
+
+        Ok(Self {
+            from: language_from_code(&metadata.from_code)?,
+            to: language_from_code(&metadata.to_code)?,
+            package_version: metadata.package_version,
+            path: path.to_path_buf(),
+            archive_dir,
+        })
+    }
+
+    /// Returns `true` if the package is an `.argosmodel` archive, which needs to be extracted before being run.
+    pub fn is_archive(&self) -> bool {
+        !self.path.is_dir()
+    }
+
+    /// Returns the directory containing the package files, extracting the archive in the given directory if needed.
+    ///
+    /// Each archive is extracted in its own subdirectory, named after the directory it contains, e.g.
+    /// `translate-en_fr-1_0`. It is extracted in a temporary directory first, then renamed, so that other processes
+    /// extracting the same package concurrently never see it partially extracted; an already extracted package is
+    /// reused as is.
+    ///
+    /// Extracted packages are returned as is.
+    pub fn extract_to<P: AsRef<Path>>(&self, directory: P) -> Result<PathBuf, Error> {
+        if !self.is_archive() {
+            return Ok(self.path.clone());
+        }
+
+        let name = match self.archive_dir.as_str() {
+            "" => self
+                .path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            archive_dir => archive_dir.to_string(),
+        };
+        let target = directory.as_ref().join(&name);
+        if target.is_dir() {
+            return Ok(target);
+        }
+
+        static EXTRACTIONS: AtomicUsize = AtomicUsize::new(0);
+        let temporary = directory.as_ref().join(format!(
+            ".{}-{}-{}.tmp",
+            name,
+            std::process::id(),
+            EXTRACTIONS.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let extracted = File::open(&self.path)
+            .map_err(|e| invalid_package(&self.path, e))
+            .and_then(|file| ZipArchive::new(file).map_err(|e| invalid_package(&self.path, e)))
+            .and_then(|mut archive| {
+                archive
+                    .extract(&temporary)
+                    .map_err(|e| invalid_package(&self.path, e))
+            })
+            .and_then(|()| {
+                // fails if another process renamed its extraction first, which is then used instead
+                match std::fs::rename(temporary.join(&self.archive_dir), &target) {
+                    Err(e) if !target.is_dir() => Err(invalid_package(&self.path, e)),
+                    _ => Ok(target),
+                }
+            });
+        let _ = std::fs::remove_dir_all(&temporary);
+
+        extracted
+    }
+}
+
+/// # CTranslate2 backend
+///
+/// The built-in [`OfflineBackend`](trait.OfflineBackend.html), running the models with
+/// [CTranslate2](https://github.com/OpenNMT/CTranslate2) on the CPU, and tokenizing the texts with
+/// [SentencePiece](https://github.com/google/sentencepiece), like Argos Translate does.
+///
+/// The model of a package is loaded the first time it is used, then kept in memory.
+/// Archives are extracted beforehand, each in its own subdirectory of the temporary directory by default,
+/// see [`ArgosPackage::extract_to`](struct.ArgosPackage.html#method.extract_to).
+///
+/// Each line of a text is translated separately, empty lines are kept as is.
+pub struct CTranslate2 {
+    cache_dir: PathBuf,
+    translators: Mutex<HashMap<PathBuf, Arc<Translator<SentencePiece>>>>,
+}
+
+impl CTranslate2 {
+    /// Returns a new [`CTranslate2`](struct.CTranslate2.html) backend, without any loaded model.
+    pub fn new() -> Self {
+        Self {
+            cache_dir: std::env::temp_dir().join("text-translator"),
+            translators: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Extracts the `.argosmodel` archives in the given directory, instead of a temporary directory.
+    pub fn with_cache_dir<P: AsRef<Path>>(mut self, cache_dir: P) -> Self {
+        self.cache_dir = cache_dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// Returns the translator of a package, loading its model if needed.
+    fn translator(&self, package: &ArgosPackage) -> Result<Arc<Translator<SentencePiece>>, Error> {
+        if let Some(translator) = self.lock().get(&package.path) {
+            return Ok(translator.clone());
+        }
+
+        // the lock is not held while loading, which can take a few seconds
+        let dir = package.extract_to(&self.cache_dir)?;
+        let tokenizer = SentencePiece::open(dir.join("sentencepiece.model"))
+            .map_err(|e| invalid_package(&package.path, e))?;
+        let translator =
+            Translator::with_tokenizer(dir.join("model"), tokenizer, &Config::default())
+                .map_err(|e| invalid_package(&package.path, e))?;
+
+        Ok(self
+            .lock()
+            .entry(package.path.clone())
+            .or_insert_with(|| Arc::new(translator))
+            .clone())
+    }
+
+    /// Locks the loaded translators, even if another thread panicked while holding the lock.
+    fn lock(&self) -> MutexGuard<'_, HashMap<PathBuf, Arc<Translator<SentencePiece>>>> {
+        self.translators
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for CTranslate2 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for CTranslate2 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CTranslate2")
+            .field("cache_dir", &self.cache_dir)
+            .field("loaded", &self.lock().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl OfflineBackend for CTranslate2 {
+    fn translate(&self, package: &ArgosPackage, text: &str) -> Result<String, Error> {
+        let translator = self.translator(package)?;
+
+        let lines: Vec<&str> = text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect();
+        let options = TranslationOptions {
+            replace_unknowns: true,
+            ..Default::default()
+        };
+        let mut translations = translator
+            .translate_batch(&lines, &options, None)
+            .map_err(|e| Error::OfflineError(OfflineError::TranslationFailed(e.to_string())))?
+            .into_iter()
+            .map(|(translation, _)| translation);
+
+        Ok(text
+            .lines()
+            .map(|line| {
+                if line.trim().is_empty() {
+                    Ok(line.to_string())
+                } else {
+                    translations.next().ok_or_else(|| {
+                        Error::OfflineError(OfflineError::TranslationFailed(
+                            "the model returned fewer translations than lines".to_string(),
+                        ))
+                    })
+                }
+            })
+            .collect::<Result<Vec<_>, Error>>()?
+            .join("\n"))
+    }
+}
+
+/// The SentencePiece tokenizer of a package, used for both the source and the target texts.
+///
+/// Unlike the tokenizer of `ct2rs`, no end of sentence token is added to the source: the models of
+/// Argos Translate are trained without it.
+struct SentencePiece(SentencePieceProcessor);
+
+impl SentencePiece {
+    fn open(path: PathBuf) -> Result<Self, sentencepiece::SentencePieceError> {
+        SentencePieceProcessor::open(path).map(Self)
+    }
+}
+
+impl ct2rs::Tokenizer for SentencePiece {
+    fn encode(&self, input: &str) -> anyhow::Result<Vec<String>> {
+        Ok(self
+            .0
+            .encode(input)?
+            .into_iter()
+            .map(|piece| piece.piece)
+            .collect())
+    }
+
+    fn decode(&self, tokens: Vec<String>) -> anyhow::Result<String> {
+        Ok(self.0.decode_pieces(&tokens)?)
+    }
+}
+
+/// # Offline engine
+///
+/// A struct representing an offline translator, running [Argos Translate](https://github.com/argosopentech/argos-translate)
+/// packages on the CPU, without any network access.
+///
+/// Packages are loaded from disk, either as `.argosmodel` archives or as extracted directories
+/// (e.g. the `~/.local/share/argos-translate/packages` directory of an Argos Translate installation).
+/// Each package translates from a language to another: when no package translates directly between two languages,
+/// the text is translated to English first, then from English to the target language.
+///
+/// The models are run by the built-in [`CTranslate2`](offline/struct.CTranslate2.html) backend, or by another
+/// [`OfflineBackend`](offline/trait.OfflineBackend.html) set with [`with_backend`](struct.Offline.html#method.with_backend).
+///
+/// Language detection is not available, so the source language must be defined.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from French to German, with the packages of an Argos Translate installation:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // load the packages, the models are loaded when first used
+/// let translator = Offline::from_dir("/home/naruto/.local/share/argos-translate/packages")?;
+///
+/// println!("installed: {:?}", translator.installed_pairs());
+///
+/// // no package translates from French to German: the text is translated to English first
+/// let translated_text: String = translator
+///     .translate(
+///         "Bonjour, je m'appelle Naruto Uzumaki!".to_string(),
+///         InputLanguage::Defined(Language::French),
+///         Language::German,
+///     )
+///     .await?;
+///
+/// assert_eq!(translated_text, "Hallo, mein Name ist Naruto Uzumaki!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Offline {
+    packages: Vec<ArgosPackage>,
+    backend: Arc<dyn OfflineBackend>,
+}
+
+impl Offline {
+    /// Returns a new [`Offline`](struct.Offline.html) struct, loading all the packages of a directory.
+    ///
+    /// Both `.argosmodel` archives and extracted package directories are loaded, other files are ignored.
+    pub fn from_dir<P: AsRef<Path>>(directory: P) -> Result<Self, Error> {
+        let directory = directory.as_ref();
+        let entries = std::fs::read_dir(directory).map_err(|e| invalid_package(directory, e))?;
+
+        let mut paths = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| invalid_package(directory, e))?.path();
+            let is_archive = path
+                .extension()
+                .is_some_and(|ext| ext == ARGOS_PACKAGE_EXTENSION);
+            if is_archive || path.join("metadata.json").is_file() {
+                paths.push(path);
+            }
+        }
+        // load the packages in a deterministic order
+        paths.sort();
+
+        let mut translator = Self::new();
+        for path in paths {
+            translator = translator.with_package(ArgosPackage::from_path(path)?);
+        }
+        Ok(translator)
+    }
+
+    /// Loads the package at the given path, see [`ArgosPackage::from_path`](offline/struct.ArgosPackage.html#method.from_path).
+    pub fn with_package_path<P: AsRef<Path>>(self, path: P) -> Result<Self, Error> {
+        Ok(self.with_package(ArgosPackage::from_path(path)?))
+    }
+
+    /// Adds a loaded package.
+    ///
+    /// A package for the same pair of languages replaces the previous one.
+    pub fn with_package(mut self, package: ArgosPackage) -> Self {
+        self.packages
+            .retain(|other| (other.from, other.to) != (package.from, package.to));
+        self.packages.push(package);
+        self
+    }
+
+    /// Runs the models with the given backend, instead of the built-in [`CTranslate2`](offline/struct.CTranslate2.html) one.
+    pub fn with_backend<B: OfflineBackend + 'static>(mut self, backend: B) -> Self {
+        self.backend = Arc::new(backend);
+        self
+    }
+
+    /// Returns the loaded packages.
+    pub fn packages(&self) -> &[ArgosPackage] {
+        &self.packages
+    }
+
+    /// Returns the `(source, target)` pairs of languages of the loaded packages.
+    ///
+    /// Pairs only available by translating through English are not included, see [`supports`](#method.supports).
+    pub fn installed_pairs(&self) -> Vec<(Language, Language)> {
+        self.packages
+            .iter()
+            .map(|package| (package.from, package.to))
+            .collect()
+    }
+
+    /// Returns `true` if a text can be translated between the two languages, directly or through English.
+    pub fn supports(&self, source_language: Language, target_language: Language) -> bool {
+        self.route(source_language, target_language).is_some()
+    }
+
+    /// Returns the package translating directly between two languages.
+    fn package(&self, from: Language, to: Language) -> Option<&ArgosPackage> {
+        self.packages
+            .iter()
+            .find(|package| package.from == from && package.to == to)
+    }
+
+    /// Returns the packages to run to translate between two languages, pivoting through English if needed.
+    fn route(&self, from: Language, to: Language) -> Option<Vec<&ArgosPackage>> {
+        if let Some(package) = self.package(from, to) {
+            return Some(vec![package]);
+        }
+
+        Some(vec![
+            self.package(from, Language::English)?,
+            self.package(Language::English, to)?,
+        ])
+    }
+}
+
+impl std::fmt::Debug for Offline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Offline")
+            .field("packages", &self.packages)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl Api for Offline {
+    /// Returns a new [`Offline`](struct.Offline.html) struct, without any package, running the models with
+    /// the built-in [`CTranslate2`](offline/struct.CTranslate2.html) backend.
+    fn new() -> Self {
+        Self {
+            packages: Vec::new(),
+            backend: Arc::new(CTranslate2::new()),
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => {
+                return Err(Error::OfflineError(OfflineError::DetectionUnsupported))
+            }
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                source
+            }
+        };
+
+        let route = self
+            .route(source_language, target_language)
+            .ok_or(Error::OfflineError(OfflineError::UnsupportedPair(
+                source_language,
+                target_language,
+            )))?;
+        let route: Vec<ArgosPackage> = route.into_iter().cloned().collect();
+        let backend = self.backend.clone();
+
+        // the models run on the CPU for a while, outside of the async runtime
+        spawn_blocking(move || {
+            let mut translation = text;
+            for package in &route {
+                translation = backend.translate(package, &translation)?;
+            }
+
+            Ok(translation)
+        })
+        .await
+        .map_err(|e| Error::OfflineError(OfflineError::TranslationFailed(e.to_string())))?
+    }
+}
+
+/// Returns the content of the `metadata.json` file of an archive, and the directory containing it.
+fn read_archive_metadata(path: &Path) -> Result<(String, String), Error> {
+    let file = File::open(path).map_err(|e| invalid_package(path, e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| invalid_package(path, e))?;
+
+    // packages contain a single top-level directory, e.g. `translate-en_fr-1_0/metadata.json`
+    let name = archive
+        .file_names()
+        .filter(|name| name.ends_with("metadata.json"))
+        .min_by_key(|name| name.matches('/').count())
+        .map(str::to_string)
+        .ok_or_else(|| invalid_package(path, "missing metadata.json"))?;
+
+    let mut metadata = String::new();
+    archive
+        .by_name(&name)
+        .map_err(|e| invalid_package(path, e))?
+        .read_to_string(&mut metadata)
+        .map_err(|e| invalid_package(path, e))?;
+
+    let archive_dir = name.trim_end_matches("metadata.json");
+    Ok((metadata, archive_dir.trim_end_matches('/').to_string()))
+}
+
+/// Returns the language of a code used in the packages metadata.
+fn language_from_code(code: &str) -> Result<Language, Error> {
+    match code {
+        "zt" => Ok(Language::Chinese),
+        "nb" => Ok(Language::Norwegian),
+        code => Language::from_language_code(code)
+            .ok_or_else(|| Error::UnknownLanguageCode(code.to_string())),
+    }
+}
+
+/// Returns an error for a package that could not be loaded.
+fn invalid_package<E: std::fmt::Display>(path: &Path, error: E) -> Error {
+    Error::OfflineError(OfflineError::InvalidPackage(format!(
+        "{}: {}",
+        path.display(),
+        error
+    )))
+}
+
+/// Enum containing different errors that may be returned by the offline engine.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum OfflineError {
+    /// A package could not be loaded, with the path and the reason.
+    InvalidPackage(String),
+    /// No loaded package translates between the two languages, even through English.
+    UnsupportedPair(Language, Language),
+    /// Language detection is not available offline, the source language must be defined.
+    DetectionUnsupported,
+    /// The model of a package could not translate a text, with the reason.
+    TranslationFailed(String),
+}
+
+impl std::fmt::Display for OfflineError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Offline error : {:?}", self)
+    }
+}
+
+impl std::error::Error for OfflineError {}
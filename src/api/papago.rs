@@ -0,0 +1,370 @@
+/*!
+A module containing the implementation of the [Papago Translation API](https://api.ncloud-docs.com/docs/en/ai-naver-papagonmt-translation) of Naver Cloud.
+
+To use it, see the [`Papago struct`](struct.Papago.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the Papago API.
+pub const PAPAGO_BASE_URL: &str = "https://papago.apigw.ntruss.com/nmt/v1";
+
+/// # Papago Translation API
+///
+/// A struct representing the [Papago Translation API](https://api.ncloud-docs.com/docs/en/ai-naver-papagonmt-translation)
+/// of Naver Cloud Platform, which does well with Korean.
+///
+/// The requests are authorized with the client ID and client secret of an application, sent as headers.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Korean:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = Papago::with_credentials("<PAPAGO_CLIENT_ID>", "<PAPAGO_CLIENT_SECRET>");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Korean)
+///     .await?;
+///
+/// assert_eq!(translated_text, "안녕하세요, 제 이름은 우즈마키 나루토입니다!");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Papago<'a> {
+    credentials: Option<(&'a str, &'a str)>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> Papago<'a> {
+    /// Returns a new [`Papago`](struct.Papago.html) struct with the given client ID and client secret.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_credentials(client_id: &'a str, client_secret: &'a str) -> Self {
+        Self {
+            credentials: Some((client_id, client_secret)),
+            base_url: PAPAGO_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`PAPAGO_BASE_URL`](constant.PAPAGO_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the client ID used by the requests, if set.
+    pub fn get_client_id(&self) -> Option<&'a str> {
+        self.credentials.map(|(client_id, _)| client_id)
+    }
+}
+
+#[async_trait]
+impl<'a> Api for Papago<'a> {
+    /// Returns a new [`Papago`](struct.Papago.html) struct without credentials.
+    fn new() -> Self {
+        Self {
+            credentials: None,
+            base_url: PAPAGO_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        let credentials = self.credentials.ok_or(Error::NoApiKeySet)?;
+
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                language_code(source)
+            }
+        };
+
+        // build query
+        let url = format!("{}/translation", self.base_url);
+        let body = format!(
+            "source={}&target={}&text={}",
+            source_language,
+            language_code(target_language),
+            encode(&text)
+        );
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body, credentials).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_text())
+    }
+}
+
+/// Returns the code used by the API for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CN",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+    (client_id, client_secret): (&str, &str),
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .header("X-NCP-APIGW-API-KEY-ID", client_id)
+        .header("X-NCP-APIGW-API-KEY", client_secret)
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::PapagoAPIError(PapagoError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a Papago translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    message: TranslateMessage,
+}
+
+/// Message in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateMessage {
+    result: TranslateResult,
+}
+
+/// Result in a TranslateMessage
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResult {
+    translated_text: String,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.message.result.translated_text.clone()
+    }
+}
+
+/// Serializable struct of a Papago error response
+///
+/// The API returns the `errorCode` and `errorMessage` at the top level, while the gateway wraps its own errors,
+/// e.g. an authentication failure, in an `error` object.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error_code: Option<String>,
+    error_message: Option<String>,
+    error: Option<GatewayError>,
+}
+
+/// Gateway error in an ErrorResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GatewayError {
+    error_code: String,
+    message: Option<String>,
+}
+
+/// Enum containing different errors that may be returned by the Papago API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum PapagoError {
+    /// Invalid request, e.g. a missing parameter.
+    BadRequest,
+    /// The source language is not supported.
+    UnsupportedSourceLanguage,
+    /// The target language is not supported.
+    UnsupportedTargetLanguage,
+    /// The pair of languages is not supported.
+    UnsupportedLanguagePair,
+    /// The text sent is too long.
+    TextTooLong,
+    /// The client ID or the client secret is invalid.
+    AuthenticationFailed,
+    /// The application is not subscribed to the Papago Translation API.
+    PermissionDenied,
+    /// The quota of the application has been exceeded.
+    QuotaExceeded,
+    /// An internal error happened.
+    ServerError,
+    UnknownErrorCode(u16),
+}
+
+impl PapagoError {
+    /// Converts an error code, as found in the `errorCode` field of the error responses (e.g. `N2MT02`), to the enum variant.
+    ///
+    /// Returns `None` if the code has no matching variant.
+    pub fn from_papago_code(code: &str) -> Option<Self> {
+        use PapagoError::*;
+        match code {
+            "N2MT01" | "N2MT03" | "N2MT05" => Some(BadRequest),
+            "N2MT02" => Some(UnsupportedSourceLanguage),
+            "N2MT04" => Some(UnsupportedTargetLanguage),
+            "N2MT06" => Some(UnsupportedLanguagePair),
+            "N2MT08" => Some(TextTooLong),
+            "N2MT07" | "N2MT99" => Some(ServerError),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for PapagoError {
+    fn from_error_code(code: u16) -> Self {
+        use PapagoError::*;
+        match code {
+            400 => BadRequest,
+            401 => AuthenticationFailed,
+            403 => PermissionDenied,
+            429 => QuotaExceeded,
+            500 => ServerError,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use PapagoError::*;
+        match self {
+            BadRequest
+            | UnsupportedSourceLanguage
+            | UnsupportedTargetLanguage
+            | UnsupportedLanguagePair
+            | TextTooLong => 400,
+            AuthenticationFailed => 401,
+            PermissionDenied => 403,
+            QuotaExceeded => 429,
+            ServerError => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `errorCode` and the message of the error response, or of the gateway error it wraps.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        let (code, message) = match from_str::<ErrorResponse>(body) {
+            Ok(ErrorResponse {
+                error_code: Some(code),
+                error_message,
+                ..
+            }) => (code, error_message),
+            Ok(ErrorResponse {
+                error: Some(error), ..
+            }) => (error.error_code, error.message),
+            _ => return ProviderError::new(Self::from_error_code(status), status),
+        };
+
+        let kind = Self::from_papago_code(&code).unwrap_or_else(|| Self::from_error_code(status));
+        let error = ProviderError::new(kind, status).with_code(&code);
+        match message {
+            Some(message) => error.with_message(&message),
+            None => error,
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use PapagoError::*;
+        match self {
+            ServerError => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use PapagoError::*;
+        match self {
+            AuthenticationFailed | PermissionDenied => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use PapagoError::*;
+        match self {
+            QuotaExceeded => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use PapagoError::*;
+        matches!(
+            self,
+            UnsupportedSourceLanguage | UnsupportedTargetLanguage | UnsupportedLanguagePair
+        )
+    }
+}
+
+impl std::fmt::Display for PapagoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Papago error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for PapagoError {}
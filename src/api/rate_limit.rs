@@ -0,0 +1,269 @@
+/*!
+A module containing a client-side rate limiter, which can be attached to any engine.
+
+To use it, see the [`RateLimited struct`](struct.RateLimited.html).
+*/
+
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::time::delay_for;
+
+use super::*;
+
+/// # Rate limiter
+///
+/// Wraps an engine to limit the number of requests and of characters it sends per second,
+/// so that many concurrent tasks stay under the quotas of the API.
+///
+/// Each limit is a token bucket: it holds up to one second of budget, refilled continuously.
+/// A request takes one token from the requests bucket, and one token per character of its text
+/// from the characters bucket; a batch takes a request token for each request the engine sends for it.
+/// When a bucket is empty, the request waits until it is refilled; the waiting requests are sent in order.
+///
+/// The limiter also counts the requests and characters sent, whether they succeed or not.
+/// This [`Usage`](rate_limit/struct.Usage.html) can be queried, and saved to be restored
+/// on the next run.
+///
+/// Cloning the limiter is cheap, and the clones share the same limits and usage:
+/// a clone can be given to each task.
+///
+/// ### Example
+///
+/// Translate many texts concurrently, sending at most 5 requests and 1000 characters per second:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = RateLimited::with_api(GoogleV2::with_key("<GOOGLE_API_KEY>"))
+///     .with_requests_per_second(5.)
+///     .with_characters_per_second(1000.);
+///
+/// let texts = vec!["Hello!", "How are you?"];
+/// let translations = futures::future::join_all(texts.into_iter().map(|text| {
+///     translator.translate(text.to_string(), InputLanguage::Automatic, Language::French)
+/// }))
+/// .await;
+///
+/// let usage = translator.get_usage();
+/// println!("{} characters sent in {} requests", usage.characters, usage.requests);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimited<A> {
+    api: A,
+    requests: Option<Arc<Mutex<TokenBucket>>>,
+    characters: Option<Arc<Mutex<TokenBucket>>>,
+    usage: Arc<Mutex<Usage>>,
+}
+
+impl<A> RateLimited<A> {
+    /// Returns a new [`RateLimited`](struct.RateLimited.html) struct wrapping the given engine, without limits.
+    pub fn with_api(api: A) -> Self {
+        Self {
+            api,
+            requests: None,
+            characters: None,
+            usage: Arc::new(Mutex::new(Usage::default())),
+        }
+    }
+
+    /// Limits the number of requests sent per second.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the rate is not a positive finite number.
+    pub fn with_requests_per_second(mut self, rate: f64) -> Self {
+        self.requests = Some(Arc::new(Mutex::new(TokenBucket::new(rate))));
+        self
+    }
+
+    /// Limits the number of characters sent per second.
+    ///
+    /// ### Panics
+    ///
+    /// Panics if the rate is not a positive finite number.
+    pub fn with_characters_per_second(mut self, rate: f64) -> Self {
+        self.characters = Some(Arc::new(Mutex::new(TokenBucket::new(rate))));
+        self
+    }
+
+    /// Starts counting from the given usage, e.g. the one saved by a previous run.
+    pub fn with_usage(self, usage: Usage) -> Self {
+        *lock(&self.usage) = usage;
+        self
+    }
+
+    /// Returns the wrapped engine.
+    pub fn get_api(&self) -> &A {
+        &self.api
+    }
+
+    /// Returns the usage counted so far.
+    pub fn get_usage(&self) -> Usage {
+        *lock(&self.usage)
+    }
+
+    /// Resets the usage, e.g. when the quota of the API is renewed, and returns the previous one.
+    pub fn reset_usage(&self) -> Usage {
+        std::mem::take(&mut *lock(&self.usage))
+    }
+
+    /// Waits until a request of the given number of characters can be sent, then counts it.
+    async fn acquire(&self, characters: usize) {
+        let wait = self
+            .requests
+            .iter()
+            .map(|bucket| lock(bucket).take(1.))
+            .chain(
+                self.characters
+                    .iter()
+                    .map(|bucket| lock(bucket).take(characters as f64)),
+            )
+            .max()
+            .unwrap_or_default();
+
+        {
+            let mut usage = lock(&self.usage);
+            usage.requests += 1;
+            usage.characters += characters as u64;
+        }
+
+        if wait > Duration::from_secs(0) {
+            delay_for(wait).await;
+        }
+    }
+}
+
+#[async_trait]
+impl<A: Api + Send + Sync> Api for RateLimited<A> {
+    /// Returns a new [`RateLimited`](struct.RateLimited.html) struct wrapping a new engine, without limits.
+    fn new() -> Self {
+        Self::with_api(A::new())
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        self.acquire(text.chars().count()).await;
+
+        self.api
+            .translate(text, source_language, target_language)
+            .await
+    }
+
+    /// Translates the texts with the batch method of the wrapped engine, one request of its
+    /// [`batch_limits`](trait.Api.html#method.batch_limits) at a time, or one by one if it has none.
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<Vec<String>, Error> {
+        // without batch limits, the engine sends a request per text
+        let (max_texts, max_characters) = self.api.batch_limits().unwrap_or((1, 0));
+
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in batch_chunks(&texts, max_texts, max_characters) {
+            self.acquire(chunk.iter().map(|text| text.chars().count()).sum())
+                .await;
+
+            match self
+                .api
+                .translate_batch(chunk.to_vec(), source_language, target_language)
+                .await
+            {
+                Ok(translations) => results.extend(translations.into_iter().map(Ok)),
+                Err(Error::BatchError(chunk_results)) => results.extend(chunk_results),
+                // detected before sending the request, the same for every chunk
+                Err(err) => return Err(err),
+            }
+        }
+
+        batch_results(results)
+    }
+
+    fn batch_limits(&self) -> Option<(usize, usize)> {
+        self.api.batch_limits()
+    }
+}
+
+#[async_trait]
+impl<A: ApiDetect + Send + Sync> ApiDetect for RateLimited<A> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        self.acquire(text.chars().count()).await;
+
+        self.api.detect(text).await
+    }
+}
+
+/// Usage of an engine counted by a [`RateLimited`](../struct.RateLimited.html) struct.
+///
+/// It can be serialized, e.g. with `serde_json`, to be saved between runs.
+#[derive(
+    Debug, Default, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize,
+)]
+pub struct Usage {
+    /// Number of requests sent.
+    pub requests: u64,
+    /// Number of characters sent.
+    pub characters: u64,
+}
+
+/// Longest wait for a token, so that a tiny rate cannot overflow the timers; the requests are stalled anyway.
+const MAX_WAIT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Token bucket holding up to one second of budget.
+#[derive(Debug)]
+struct TokenBucket {
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(rate: f64) -> Self {
+        assert!(
+            rate.is_finite() && rate > 0.,
+            "the rate of a limit must be a positive finite number, got {}",
+            rate
+        );
+
+        Self {
+            rate,
+            tokens: rate,
+            updated: Instant::now(),
+        }
+    }
+
+    /// Takes the given number of tokens, and returns the time to wait before they are available.
+    ///
+    /// The tokens are reserved even if they are not available yet, so that the next callers wait after this one.
+    fn take(&mut self, tokens: f64) -> Duration {
+        let now = Instant::now();
+        let refilled = now.duration_since(self.updated).as_secs_f64() * self.rate;
+        self.tokens = (self.tokens + refilled).min(self.rate) - tokens;
+        self.updated = now;
+
+        if self.tokens >= 0. {
+            Duration::from_secs(0)
+        } else {
+            Duration::try_from_secs_f64(-self.tokens / self.rate)
+                .map_or(MAX_WAIT, |wait| wait.min(MAX_WAIT))
+        }
+    }
+}
+
+/// Locks a mutex, even if another task panicked while holding it.
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
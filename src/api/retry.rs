@@ -0,0 +1,286 @@
+/*!
+A module containing the retry policy of the HTTP client.
+
+To use it, see the [`RetryPolicy struct`](struct.RetryPolicy.html).
+*/
+
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, SystemTime};
+
+use http::{header::RETRY_AFTER, Request, Response};
+use tokio::time::delay_for;
+
+use super::*;
+
+/// Status codes retried by default: rate limiting and transient server errors.
+pub const DEFAULT_RETRYABLE_STATUSES: &[u16] = &[429, 500, 502, 503, 504];
+
+/// Returns whether an error is retried by default, see [`Error::is_retryable`](../enum.Error.html#method.is_retryable).
+///
+/// The requests that could not be sent at all are retried, e.g. when the connection was refused or reset,
+/// and the ones that timed out. The ones that could not be built, failing with a `RequestError`, are not.
+/// The errors reported by an API in a successful response are retried if they are transient, e.g. a Baidu
+/// access frequency limit.
+pub fn default_retryable_error(error: &Error) -> bool {
+    error.is_retryable()
+}
+
+/// # Retry policy
+///
+/// Describes how an [`HttpClient`](struct.HttpClient.html) retries the requests failing with a transient error,
+/// e.g. a rate limiting or a temporarily unavailable server.
+///
+/// A request is retried when:
+///
+/// - the API answers with a retryable status code, [`DEFAULT_RETRYABLE_STATUSES`](retry/constant.DEFAULT_RETRYABLE_STATUSES.html)
+///   by default
+/// - the transport returns a retryable error, as decided by [`default_retryable_error`](retry/fn.default_retryable_error.html)
+///   by default
+/// - the API reports a retryable error, decided the same way, in a response with a `200` status code, as Baidu, Youdao
+///   and MyMemory do
+///
+/// The delay between two attempts grows exponentially from the base delay, up to the maximum delay.
+/// With jitter, a random delay between half and the whole of it is waited instead, so that many clients
+/// do not retry at the same time. If the API tells how long to wait with a `Retry-After` header, this delay
+/// is used instead; if it is longer than the maximum delay, the request is not retried.
+///
+/// Once the attempts are exhausted, the last response is handled by the engine as usual, e.g. returned as an
+/// API error.
+///
+/// The retried requests are sent again as is: this is safe for translations and detections, but could
+/// duplicate the side effects of other requests, e.g. the ones adding content to a translation memory.
+///
+/// ### Example
+///
+/// Retry the requests up to 5 times, waiting 1 second before the first retry:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let client = HttpClient::new().with_retry(
+///     RetryPolicy::new()
+///         .with_max_attempts(5)
+///         .with_base_delay(Duration::from_secs(1)),
+/// );
+/// let translator = GoogleV2::with_key("<GOOGLE_API_KEY>").with_client(&client);
+///
+/// let translated_text = translator
+///     .translate("Hello".to_string(), InputLanguage::Automatic, Language::French)
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: bool,
+    retry_after: bool,
+    retryable_statuses: &'static [u16],
+    retryable_error: fn(&Error) -> bool,
+}
+
+impl RetryPolicy {
+    /// Returns the default policy: 3 attempts, starting with a 500 ms delay up to 30 seconds,
+    /// with jitter and honoring `Retry-After`.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn new() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            retry_after: true,
+            retryable_statuses: DEFAULT_RETRYABLE_STATUSES,
+            retryable_error: default_retryable_error,
+        }
+    }
+
+    /// Sets the maximum number of attempts, including the first one. `1` disables the retries.
+    pub const fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Sets the delay before the first retry, doubled for each following one.
+    pub const fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets whether the delays are randomized.
+    pub const fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets whether the delay given by a `Retry-After` header is used.
+    pub const fn with_retry_after(mut self, retry_after: bool) -> Self {
+        self.retry_after = retry_after;
+        self
+    }
+
+    /// Sets the status codes of the responses to retry.
+    pub const fn with_retryable_statuses(mut self, statuses: &'static [u16]) -> Self {
+        self.retryable_statuses = statuses;
+        self
+    }
+
+    /// Sets the function deciding whether an error is retried: either returned by the transport, or reported by
+    /// the API in a successful response.
+    pub const fn with_retryable_errors(mut self, retryable: fn(&Error) -> bool) -> Self {
+        self.retryable_error = retryable;
+        self
+    }
+
+    /// Returns the maximum number of attempts, including the first one.
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the given retry, starting from `1`, without jitter.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1u32
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Returns the delay to wait before the given retry, or `None` if the request must not be retried.
+    fn delay(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        match retry_after {
+            Some(delay) if self.retry_after => {
+                if delay <= self.max_delay {
+                    Some(delay)
+                } else {
+                    None
+                }
+            }
+            _ => {
+                let delay = self.backoff(retry);
+                if self.jitter {
+                    let half = delay / 2;
+                    Some(half + half.mul_f64(random_fraction()))
+                } else {
+                    Some(delay)
+                }
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sends a request with the given transport, retrying it as described by the policy.
+pub(crate) async fn send(
+    transport: &dyn Transport,
+    policy: &RetryPolicy,
+    request: Request<Vec<u8>>,
+) -> Result<Response<Vec<u8>>, Error> {
+    let (parts, body) = request.into_parts();
+    let mut attempt = 1;
+
+    loop {
+        // the request is consumed by the transport, rebuild it for each attempt
+        let mut request = Request::new(body.clone());
+        *request.method_mut() = parts.method.clone();
+        *request.uri_mut() = parts.uri.clone();
+        *request.version_mut() = parts.version;
+        *request.headers_mut() = parts.headers.clone();
+
+        let result = transport.send(request).await;
+
+        let retry_after = match &result {
+            Ok(res) if policy.retryable_statuses.contains(&res.status().as_u16()) => {
+                retry_after(res)
+            }
+            Err(err) if (policy.retryable_error)(err) => None,
+            _ => return result,
+        };
+
+        if attempt >= policy.max_attempts {
+            return result;
+        }
+
+        match policy.delay(attempt, retry_after) {
+            Some(delay) => delay_for(delay).await,
+            None => return result,
+        }
+
+        attempt += 1;
+    }
+}
+
+/// Runs a request and the parsing of its response, retrying it as described by the policy of the client when the API
+/// reports a retryable error in a successful response, which the client cannot tell apart from a translation.
+///
+/// The other errors are returned as is: the failed responses have been retried by the client already.
+pub(crate) async fn run<T, F, Fut>(client: Option<&HttpClient>, attempt: F) -> Result<T, Error>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, Error>>,
+{
+    let policy = match client.and_then(HttpClient::get_retry_policy) {
+        Some(policy) => *policy,
+        None => return attempt().await,
+    };
+    let mut retry = 1;
+
+    loop {
+        let result = attempt().await;
+
+        match &result {
+            Err(err)
+                if retry < policy.max_attempts
+                    && err.provider_status() == Some(200)
+                    && (policy.retryable_error)(err) => {}
+            _ => return result,
+        }
+
+        match policy.delay(retry, None) {
+            Some(delay) => delay_for(delay).await,
+            None => return result,
+        }
+
+        retry += 1;
+    }
+}
+
+/// Returns the delay given by the `Retry-After` header of a response, either in seconds or as a date.
+fn retry_after(response: &Response<Vec<u8>>) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    match value.parse::<u64>() {
+        Ok(seconds) => Some(Duration::from_secs(seconds)),
+        Err(_) => httpdate::parse_http_date(value)
+            .ok()
+            .map(|date| date.duration_since(SystemTime::now()).unwrap_or_default()),
+    }
+}
+
+/// Returns a random number between 0 and 1.
+fn random_fraction() -> f64 {
+    // each new `RandomState` is randomly seeded
+    let random = RandomState::new().build_hasher().finish();
+
+    (random >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,116 @@
+/*!
+A module containing the timeouts of the HTTP requests.
+
+To use it, see the [`Timeouts struct`](struct.Timeouts.html).
+*/
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::timeout;
+
+use super::*;
+
+/// # Timeouts
+///
+/// The timeouts of the requests sent by a [`HyperTransport`](struct.HyperTransport.html):
+///
+/// - connect: the maximum time to open a connection to the server
+/// - read: the maximum time waiting for the server, for the response headers once the request is sent
+///   (including the time to connect, if no pooled connection is available), then for each chunk of the body
+/// - total: the maximum time of a whole request, from connecting to reading the whole response
+///
+/// A request timing out returns an [`Error::Timeout`](enum.Error.html#variant.Timeout). With a
+/// [`RetryPolicy`](struct.RetryPolicy.html), it is retried by default.
+///
+/// By default, the connections time out after 10 seconds and the reads after 30 seconds, without total timeout.
+///
+/// ### Example
+///
+/// Give up the requests lasting more than 5 seconds:
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use text_translator::*;
+///
+/// let client = HttpClient::with_timeouts(Timeouts::new().with_total_timeout(Duration::from_secs(5)));
+/// let translator = DeepL::with_key("<DEEPL_API_KEY>").with_client(&client);
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Timeouts {
+    connect: Option<Duration>,
+    read: Option<Duration>,
+    total: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Returns the default timeouts: 10 seconds to connect, 30 seconds to read, and no total timeout.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn new() -> Self {
+        Self {
+            connect: Some(Duration::from_secs(10)),
+            read: Some(Duration::from_secs(30)),
+            total: None,
+        }
+    }
+
+    /// Returns timeouts that never expire, waiting for the server as long as needed.
+    pub const fn none() -> Self {
+        Self {
+            connect: None,
+            read: None,
+            total: None,
+        }
+    }
+
+    /// Sets the maximum time to open a connection.
+    pub const fn with_connect_timeout(mut self, connect: Duration) -> Self {
+        self.connect = Some(connect);
+        self
+    }
+
+    /// Sets the maximum time waiting for the response headers, then for each chunk of the body.
+    pub const fn with_read_timeout(mut self, read: Duration) -> Self {
+        self.read = Some(read);
+        self
+    }
+
+    /// Sets the maximum time of a whole request.
+    pub const fn with_total_timeout(mut self, total: Duration) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Returns the connect timeout, if set.
+    pub fn get_connect_timeout(&self) -> Option<Duration> {
+        self.connect
+    }
+
+    /// Returns the read timeout, if set.
+    pub fn get_read_timeout(&self) -> Option<Duration> {
+        self.read
+    }
+
+    /// Returns the total timeout, if set.
+    pub fn get_total_timeout(&self) -> Option<Duration> {
+        self.total
+    }
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Awaits a future, returning an [`Error::Timeout`](../enum.Error.html#variant.Timeout) if it lasts longer than the given duration.
+pub(crate) async fn within<F: Future>(
+    duration: Option<Duration>,
+    future: F,
+) -> Result<F::Output, Error> {
+    match duration {
+        Some(duration) => timeout(duration, future).await.map_err(|_| Error::Timeout),
+        None => Ok(future.await),
+    }
+}
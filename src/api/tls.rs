@@ -0,0 +1,125 @@
+/*!
+A module containing the TLS backends of the [`HyperTransport`](../struct.HyperTransport.html).
+
+The backend is selected at compile time, with exactly one of the following features, checked by the build script:
+
+- `native-tls` (default): the library of the system, e.g. OpenSSL on Linux, trusting the root certificates of the system
+- `rustls`: rustls, trusting the webpki root certificates, or the ones of the system with `rustls-native-roots`
+*/
+
+use hyper::client::HttpConnector;
+use hyper_proxy::ProxyConnector;
+
+#[cfg(feature = "native-tls")]
+pub(crate) use hyper_tls::HttpsConnector;
+
+#[cfg(feature = "rustls")]
+pub(crate) use hyper_rustls::HttpsConnector;
+
+use super::*;
+
+/// The TLS configuration of the backend.
+#[cfg(feature = "native-tls")]
+pub(crate) type TlsConnector = native_tls::TlsConnector;
+
+/// The TLS configuration of the backend.
+#[cfg(feature = "rustls")]
+pub(crate) type TlsConnector = std::sync::Arc<rustls::ClientConfig>;
+
+/// Returns the default TLS configuration, trusting the default root certificates of the backend.
+#[cfg(feature = "native-tls")]
+pub(crate) fn default_connector() -> Result<TlsConnector, Error> {
+    native_tls::TlsConnector::new().map_err(|e| Error::TlsError(e.to_string()))
+}
+
+/// Returns the default TLS configuration, trusting the default root certificates of the backend.
+#[cfg(feature = "rustls")]
+pub(crate) fn default_connector() -> Result<TlsConnector, Error> {
+    Ok(std::sync::Arc::new(default_config()?))
+}
+
+/// Returns the default TLS configuration, also trusting the root certificates of the given PEM bundle.
+#[cfg(feature = "native-tls")]
+pub(crate) fn with_root_certificates(pem: &[u8]) -> Result<TlsConnector, Error> {
+    let mut builder = native_tls::TlsConnector::builder();
+
+    for certificate in pem_certificates(pem)? {
+        let certificate = native_tls::Certificate::from_pem(certificate.as_bytes())
+            .map_err(|e| Error::TlsError(e.to_string()))?;
+        builder.add_root_certificate(certificate);
+    }
+
+    builder.build().map_err(|e| Error::TlsError(e.to_string()))
+}
+
+/// Returns the default TLS configuration, also trusting the root certificates of the given PEM bundle.
+#[cfg(feature = "rustls")]
+pub(crate) fn with_root_certificates(pem: &[u8]) -> Result<TlsConnector, Error> {
+    let mut config = default_config()?;
+
+    for certificate in pem_certificates(pem)? {
+        match config.root_store.add_pem_file(&mut certificate.as_bytes()) {
+            Ok((valid, 0)) if valid > 0 => {}
+            _ => return Err(Error::TlsError("invalid certificate".to_string())),
+        }
+    }
+
+    Ok(std::sync::Arc::new(config))
+}
+
+/// Returns a connector opening HTTPS connections, directly or through the tunnels of a proxy.
+pub(crate) fn connector(
+    http: HttpConnector,
+    tls: &TlsConnector,
+) -> ProxyConnector<HttpsConnector<HttpConnector>> {
+    #[cfg(feature = "native-tls")]
+    let https = HttpsConnector::from((http, tokio_tls::TlsConnector::from(tls.clone())));
+    #[cfg(feature = "rustls")]
+    let https = HttpsConnector::from((http, tls.clone()));
+
+    let mut connector = ProxyConnector::unsecured(https);
+    // the tunnels to the APIs are secured with the same TLS configuration
+    #[cfg(feature = "native-tls")]
+    connector.set_tls(Some(tls.clone()));
+    #[cfg(feature = "rustls")]
+    connector.set_tls(Some(tls.clone().into()));
+
+    connector
+}
+
+/// Returns the rustls configuration trusting the webpki root certificates, or the ones of the system
+/// with the `rustls-native-roots` feature.
+#[cfg(feature = "rustls")]
+fn default_config() -> Result<rustls::ClientConfig, Error> {
+    let mut config = rustls::ClientConfig::new();
+
+    #[cfg(feature = "rustls-native-roots")]
+    {
+        config.root_store = match rustls_native_certs::load_native_certs() {
+            Ok(store) | Err((Some(store), _)) => store,
+            Err((None, err)) => return Err(Error::TlsError(err.to_string())),
+        };
+    }
+    #[cfg(not(feature = "rustls-native-roots"))]
+    config
+        .root_store
+        .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+
+    Ok(config)
+}
+
+/// Splits a PEM bundle into its certificates.
+fn pem_certificates(pem: &[u8]) -> Result<Vec<&str>, Error> {
+    let pem = std::str::from_utf8(pem).map_err(Error::CouldNotConvertToUtf8Str)?;
+
+    let certificates: Vec<&str> = pem
+        .split_inclusive("-----END CERTIFICATE-----")
+        .filter(|block| block.contains("-----BEGIN CERTIFICATE-----"))
+        .collect();
+
+    if certificates.is_empty() {
+        Err(Error::TlsError("no certificate found".to_string()))
+    } else {
+        Ok(certificates)
+    }
+}
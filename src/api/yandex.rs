@@ -2,11 +2,14 @@
 A module containing the implementation of the [Yandex Translate API](https://tech.yandex.com/translate/doc/dg/concepts/about-docpage).
 
 To use it, see the [`Yandex struct`](struct.Yandex.html).
+
+*__Deprecated:__ the v1.5 API has been shut down, use [`YandexCloud`](../yandex_cloud/struct.YandexCloud.html) instead.*
 */
 
-use http::uri::Uri;
-use hyper::{body::to_bytes, client::Client};
-use hyper_tls::HttpsConnector;
+#![allow(deprecated)]
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
 use urlencoding::encode;
@@ -16,15 +19,22 @@ use super::*;
 /// Base URL used to access the Yandex API.
 pub const BASE_URL: &str = "https://translate.yandex.net/api/v1.5/tr.json/";
 
+/// Maximum number of characters sent in a single request by [`translate_batch`](../trait.Api.html#method.translate_batch),
+/// the limit of the API.
+pub const MAX_BATCH_CHARACTERS: usize = 10_000;
+
 /// # Yandex Translate API
 ///
 /// A struct representing the [Yandex Translate API](https://tech.yandex.com/translate/doc/dg/concepts/about-docpage).
 ///
 /// This API needs a key, which can be provided at [this page](https://translate.yandex.com/developers/keys).
+/// The keyless endpoint of the Yandex web pages is deliberately not supported, as it needs a session id scraped
+/// from these pages: use [`GoogleWeb`](../google_web/struct.GoogleWeb.html) with the `keyless` feature instead.
 ///
 /// It implements:
 ///
-/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language translation, with the default [`Api`](../trait.Api.html) trait, translating up to
+///   [`MAX_BATCH_CHARACTERS`](constant.MAX_BATCH_CHARACTERS.html) characters of texts per request with `translate_batch`
 /// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
 /// - API key, with the [`ApiKey`](../trait.ApiDetect.html) trait
 ///
@@ -82,9 +92,12 @@ pub const BASE_URL: &str = "https://translate.yandex.net/api/v1.5/tr.json/";
 ///
 /// assert_eq!(detected_language, Language::French)
 /// ```
+#[deprecated(note = "the Yandex Translate v1.5 API has been shut down, use `YandexCloud` instead")]
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Yandex<'a> {
     key: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
 }
 
 impl<'a> Yandex<'a> {
@@ -92,7 +105,26 @@ impl<'a> Yandex<'a> {
     ///
     /// Can be used in constant definitions.
     pub const fn with_key(key: &'a str) -> Self {
-        Self { key: Some(key) }
+        Self {
+            key: Some(key),
+            base_url: BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`BASE_URL`](constant.BASE_URL.html),
+    /// e.g. a regional endpoint, a proxy or a local mock server.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
     }
 }
 
@@ -112,7 +144,11 @@ impl<'a> Api for Yandex<'a> {
     ///
     /// To set it, use [`with_key`](struct.Yandex.html#method.with_key) or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
     fn new() -> Self {
-        Self { key: None }
+        Self {
+            key: None,
+            base_url: BASE_URL,
+            client: None,
+        }
     }
 
     // TODO make `translate` async
@@ -122,34 +158,61 @@ impl<'a> Api for Yandex<'a> {
         source_language: InputLanguage,
         target_language: Language,
     ) -> Result<String, Error> {
-        // get translation direction
-        let translation_languages = match source_language {
-            InputLanguage::Automatic => target_language.to_language_code().into(),
-            InputLanguage::Defined(source) => {
-                // verify that source languages != target language
-                if source == target_language {
-                    return Err(Error::SameLanguages(source, target_language));
-                } else {
-                    format!(
-                        "{}-{}",
-                        source.to_language_code(),
-                        target_language.to_language_code()
-                    )
+        let translation_languages = translation_direction(source_language, target_language)?;
+
+        let json_body = self
+            .send_translate(std::slice::from_ref(&text), &translation_languages)
+            .await?;
+
+        Ok(json_body.get_text())
+    }
+
+    fn batch_limits(&self) -> Option<(usize, usize)> {
+        Some((usize::MAX, MAX_BATCH_CHARACTERS))
+    }
+
+    /// Translates the texts with a request per [`MAX_BATCH_CHARACTERS`](constant.MAX_BATCH_CHARACTERS.html)
+    /// characters, repeating the `text` field of the url-encoded body.
+    async fn translate_batch(
+        &self,
+        texts: Vec<String>,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<Vec<String>, Error> {
+        let translation_languages = translation_direction(source_language, target_language)?;
+        self.key.ok_or(Error::NoApiKeySet)?;
+
+        let mut results = Vec::with_capacity(texts.len());
+        for chunk in batch_chunks(&texts, usize::MAX, MAX_BATCH_CHARACTERS) {
+            match self.send_translate(chunk, &translation_languages).await {
+                Ok(json_body) if json_body.text.len() == chunk.len() => {
+                    results.extend(json_body.text.into_iter().map(Ok))
                 }
+                Ok(_) => results.extend(chunk.iter().map(|_| Err(Error::CouldNotDerializeJson))),
+                Err(err) => results.extend(chunk.iter().map(|_| Err(err.clone()))),
             }
-        };
+        }
+
+        batch_results(results)
+    }
+}
 
+impl<'a> Yandex<'a> {
+    /// Sends a translation request of the given texts, in the given translation direction.
+    async fn send_translate(
+        &self,
+        texts: &[String],
+        translation_languages: &str,
+    ) -> Result<TranslateResponse, Error> {
         // build query
-        let mut query: String = String::from(BASE_URL);
-        query = format!(
-            "{}translate?key={}&lang={}&text={}",
-            query,
+        let query = format!(
+            "{}/translate?key={}&lang={}",
+            self.base_url.trim_end_matches('/'),
             match self.key {
                 Some(key) => key,
                 None => return Err(Error::NoApiKeySet),
             },
             translation_languages,
-            encode(text.as_str())
         );
 
         let uri = match query.parse::<Uri>() {
@@ -157,14 +220,41 @@ impl<'a> Api for Yandex<'a> {
             Err(_) => return Err(Error::CouldNotParseUri(query)),
         };
 
-        let body = get_response(uri).await?;
+        // the texts are sent in the body, which is not limited in size like the URI
+        let form = texts
+            .iter()
+            .map(|text| format!("text={}", encode(text.as_str())))
+            .collect::<Vec<_>>()
+            .join("&");
 
-        let json_body: TranslateResponse = match from_str(body.as_str()) {
-            Ok(res) => res,
-            Err(_) => return Err(Error::CouldNotDerializeJson),
-        };
+        let body = get_response(self.client, uri, Some(form)).await?;
 
-        Ok(json_body.get_text())
+        match from_str(body.as_str()) {
+            Ok(res) => Ok(res),
+            Err(err) => Err(Error::InvalidResponse(ErrorSource::new(err))),
+        }
+    }
+}
+
+/// Returns the translation direction, e.g. `en-fr`, or only the target language to detect the source one.
+fn translation_direction(
+    source_language: InputLanguage,
+    target_language: Language,
+) -> Result<String, Error> {
+    match source_language {
+        InputLanguage::Automatic => Ok(target_language.to_language_code().into()),
+        InputLanguage::Defined(source) => {
+            // verify that source languages != target language
+            if source == target_language {
+                Err(Error::SameLanguages(source, target_language))
+            } else {
+                Ok(format!(
+                    "{}-{}",
+                    source.to_language_code(),
+                    target_language.to_language_code()
+                ))
+            }
+        }
     }
 }
 
@@ -173,10 +263,9 @@ impl<'a> ApiDetect for Yandex<'a> {
     // TODO make `detect` async
     async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
         // build query
-        let mut query: String = String::from(BASE_URL);
-        query = format!(
-            "{}detect?key={}&text={}",
-            query,
+        let query = format!(
+            "{}/detect?key={}&text={}",
+            self.base_url.trim_end_matches('/'),
             match self.key {
                 Some(key) => key,
                 None => return Err(Error::NoApiKeySet),
@@ -189,11 +278,11 @@ impl<'a> ApiDetect for Yandex<'a> {
             Err(_) => return Err(Error::CouldNotParseUri(query)),
         };
 
-        let body = get_response(uri).await?;
+        let body = get_response(self.client, uri, None).await?;
 
         let json_body: DetectResponse = match from_str(body.as_str()) {
             Ok(res) => res,
-            Err(_) => return Err(Error::CouldNotDerializeJson),
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
         };
 
         Ok(json_body.get_lang())
@@ -201,21 +290,42 @@ impl<'a> ApiDetect for Yandex<'a> {
 }
 
 /// Returns the response json body, needed to be deserialized.
-async fn get_response(uri: Uri) -> Result<String, Error> {
-    let https = HttpsConnector::new();
-    let client = Client::builder().build::<_, hyper::Body>(https);
+///
+/// The request is a `GET`, or a `POST` of the given url-encoded form.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    form: Option<String>,
+) -> Result<String, Error> {
+    let req = match form {
+        Some(form) => Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::from(form)),
+        None => Request::builder()
+            .method("GET")
+            .uri(uri)
+            .body(Body::empty()),
+    }
+    .map_err(|e| Error::RequestError(e.to_string()))?;
 
-    let res = client.get(uri).await.unwrap();
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
 
-    match res.status().as_u16() {
-        200 => (),
-        error => return Err(Error::YandexAPIError(YandexError::from_error_code(error))),
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
     };
 
-    let body = to_bytes(res.into_body()).await.unwrap();
-    match std::str::from_utf8(&body) {
-        Ok(res) => Ok(res.to_string()),
-        Err(err) => Err(Error::CouldNotConvertToUtf8Str(err)),
+    match status {
+        200 => Ok(body),
+        status => Err(Error::YandexAPIError(YandexError::from_response(
+            status, &body,
+        ))),
     }
 }
 
@@ -244,6 +354,13 @@ impl ApiDetectResponse for DetectResponse {
     }
 }
 
+/// Serializable struct of a Yandex error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
 /// Enum containing different errors that may be returned by the Yandex API.
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
 pub enum YandexError {
@@ -259,7 +376,7 @@ pub enum YandexError {
 impl ApiError for YandexError {
     fn from_error_code(code: u16) -> Self {
         use YandexError::*;
-        match code - 1 {
+        match code {
             401 => InvalidAPIKey,
             402 => BlockedAPIKey,
             404 => DailyLimitExceeded,
@@ -282,11 +399,52 @@ impl ApiError for YandexError {
             UnknownErrorCode(other) => *other,
         }
     }
+
+    /// Parses the `code` and `message` of the error response, the code being the documented one.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => ProviderError::new(Self::from_error_code(res.code), status)
+                .with_code(&res.code.to_string())
+                .with_message(&res.message),
+            Err(_) => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use YandexError::*;
+        match self {
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use YandexError::*;
+        match self {
+            InvalidAPIKey | BlockedAPIKey => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use YandexError::*;
+        match self {
+            DailyLimitExceeded => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use YandexError::*;
+        matches!(self, TranslationDirectionNotSupported)
+    }
 }
 
 impl std::fmt::Display for YandexError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error : {}", &self)
+        write!(f, "Yandex error {} : {:?}", self.to_error_code(), self)
     }
 }
 
@@ -0,0 +1,536 @@
+/*!
+A module containing the implementation of the [Yandex Cloud Translate API v2](https://cloud.yandex.com/en/docs/translate/api-ref/).
+
+To use it, see the [`YandexCloud struct`](struct.YandexCloud.html).
+*/
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+
+use super::*;
+
+/// Base URL used to access the Yandex Cloud Translate API.
+pub const YANDEX_CLOUD_BASE_URL: &str = "https://translate.api.cloud.yandex.net/translate/v2";
+
+/// Helper structure of the request body of a Yandex Cloud translate request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder_id: Option<&'a str>,
+    texts: &'a [String],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_language_code: Option<&'a str>,
+    target_language_code: &'a str,
+    format: &'static str,
+}
+
+/// Helper structure of the request body of a Yandex Cloud detect request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder_id: Option<&'a str>,
+    text: &'a str,
+}
+
+/// Helper structure of the request body of a Yandex Cloud languages request
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LanguagesRequestBody<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    folder_id: Option<&'a str>,
+}
+
+/// Credentials used to authorize the requests to the Yandex Cloud API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum YandexCloudAuth<'a> {
+    /// API key of a service account, sent as `Api-Key <key>`.
+    ApiKey(&'a str),
+    /// IAM token, sent as `Bearer <token>`.
+    IamToken(&'a str),
+}
+
+impl<'a> YandexCloudAuth<'a> {
+    /// Returns the value of the `Authorization` header.
+    fn to_header(self) -> String {
+        match self {
+            YandexCloudAuth::ApiKey(key) => format!("Api-Key {}", key),
+            YandexCloudAuth::IamToken(token) => format!("Bearer {}", token),
+        }
+    }
+}
+
+/// # Yandex Cloud Translate API
+///
+/// A struct representing the [Yandex Cloud Translate API](https://cloud.yandex.com/en/docs/translate/), which
+/// replaces the discontinued v1.5 API used by [`Yandex`](struct.Yandex.html).
+///
+/// The requests can be authorized with either:
+///
+/// - the API key of a service account, with [`with_key`](struct.YandexCloud.html#method.with_key);
+/// - an IAM token, with [`with_iam_token`](struct.YandexCloud.html#method.with_iam_token). In this case,
+///   the ID of the folder must be set with [`with_folder_id`](struct.YandexCloud.html#method.with_folder_id).
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+/// - language detection, with the [`ApiDetect`](../trait.ApiDetect.html) trait
+/// - API key, with the [`ApiKey`](../trait.ApiKey.html) trait
+///
+/// Several texts can be translated in a single request with [`translate_texts`](struct.YandexCloud.html#method.translate_texts),
+/// and the supported languages are listed by [`languages`](struct.YandexCloud.html#method.languages).
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Japanese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = YandexCloud::with_iam_token("<IAM_TOKEN>").with_folder_id("<FOLDER_ID>");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Japanese)
+///     .await?;
+///
+/// assert_eq!(translated_text, "こんにちは、私の名前はうずまきナルトです！");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ### Language detection
+///
+/// Detect the language of a text:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// let translator = YandexCloud::with_key("<YANDEX_CLOUD_API_KEY>");
+/// let text: String = "Bonjour, je m'appelle Naruto Uzumaki!".to_string();
+///
+/// // detect the language, returns a `Result<Option<Language>, Error>`
+/// let detected_language = translator.detect(text).await?;
+///
+/// assert_eq!(detected_language, Some(Language::French));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct YandexCloud<'a> {
+    auth: Option<YandexCloudAuth<'a>>,
+    folder_id: Option<&'a str>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> YandexCloud<'a> {
+    /// Returns a new [`YandexCloud`](struct.YandexCloud.html) struct with the given service account API key.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_key(key: &'a str) -> Self {
+        Self {
+            auth: Some(YandexCloudAuth::ApiKey(key)),
+            folder_id: None,
+            base_url: YANDEX_CLOUD_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Returns a new [`YandexCloud`](struct.YandexCloud.html) struct with the given IAM token.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_iam_token(token: &'a str) -> Self {
+        Self {
+            auth: Some(YandexCloudAuth::IamToken(token)),
+            folder_id: None,
+            base_url: YANDEX_CLOUD_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sets the ID of the folder, sent as `folderId` in every request.
+    pub const fn with_folder_id(mut self, folder_id: &'a str) -> Self {
+        self.folder_id = Some(folder_id);
+        self
+    }
+
+    /// Sends the requests to the given base URL instead of [`YANDEX_CLOUD_BASE_URL`](constant.YANDEX_CLOUD_BASE_URL.html).
+    ///
+    /// The URL must not end with a slash.
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the credentials used by the requests, if set.
+    pub fn get_auth(&self) -> Option<YandexCloudAuth<'a>> {
+        self.auth
+    }
+
+    /// Translates several texts between two languages, in a single request.
+    ///
+    /// Returns the translations in the same order as `texts`.
+    pub async fn translate_texts(
+        &self,
+        texts: Vec<String>,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<Vec<String>, Error> {
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => None,
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                Some(source.to_language_code())
+            }
+        };
+
+        let body = serde_json::to_string(&TranslateRequestBody {
+            folder_id: self.folder_id,
+            texts: &texts,
+            source_language_code: source_language,
+            target_language_code: target_language.to_language_code(),
+            format: "PLAIN_TEXT",
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let body = get_response(self, "translate", body).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+        if json_body.translations.len() != texts.len() {
+            return Err(Error::CouldNotDerializeJson);
+        }
+
+        Ok(json_body
+            .translations
+            .into_iter()
+            .map(|translation| translation.text)
+            .collect())
+    }
+
+    /// Returns the languages supported by the API.
+    ///
+    /// Languages returned by the API but not represented by [`Language`](../enum.Language.html) are skipped.
+    pub async fn languages(&self) -> Result<Vec<Language>, Error> {
+        let body = serde_json::to_string(&LanguagesRequestBody {
+            folder_id: self.folder_id,
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let body = get_response(self, "languages", body).await?;
+
+        let json_body: LanguagesResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body
+            .languages
+            .iter()
+            .filter_map(|language| Language::from_language_code(&language.code))
+            .collect())
+    }
+}
+
+impl<'a> ApiKey<'a> for YandexCloud<'a> {
+    /// Sets the service account API key, replacing any IAM token.
+    fn set_set(&mut self, key: &'a str) {
+        self.auth = Some(YandexCloudAuth::ApiKey(key))
+    }
+
+    /// Returns the API key or IAM token used by the requests.
+    fn get_key(&self) -> Option<&'a str> {
+        match self.auth {
+            Some(YandexCloudAuth::ApiKey(key)) | Some(YandexCloudAuth::IamToken(key)) => Some(key),
+            None => None,
+        }
+    }
+}
+
+#[async_trait]
+impl<'a> Api for YandexCloud<'a> {
+    /// Returns a new [`YandexCloud`](struct.YandexCloud.html) struct without credentials.
+    ///
+    /// To set them, use [`with_key`](struct.YandexCloud.html#method.with_key), [`with_iam_token`](struct.YandexCloud.html#method.with_iam_token)
+    /// or [`set_key`](../trait.ApiKey.html#tymethod.set_set) methods instead.
+    fn new() -> Self {
+        Self {
+            auth: None,
+            folder_id: None,
+            base_url: YANDEX_CLOUD_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        self.translate_texts(vec![text], source_language, target_language)
+            .await?
+            .pop()
+            .ok_or(Error::CouldNotDerializeJson)
+    }
+}
+
+#[async_trait]
+impl<'a> ApiDetect for YandexCloud<'a> {
+    async fn detect(&self, text: String) -> Result<Option<Language>, Error> {
+        let body = serde_json::to_string(&DetectRequestBody {
+            folder_id: self.folder_id,
+            text: &text,
+        })
+        .map_err(|_| Error::CouldNotSerializeJson)?;
+
+        let body = get_response(self, "detect", body).await?;
+
+        let json_body: DetectResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        Ok(json_body.get_lang())
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    translator: &YandexCloud<'_>,
+    method: &str,
+    body: String,
+) -> Result<String, Error> {
+    let auth = translator.auth.ok_or(Error::NoApiKeySet)?;
+
+    let url = format!("{}/{}", translator.base_url, method);
+    let uri = match url.parse::<Uri>() {
+        Ok(res) => res,
+        Err(_) => return Err(Error::CouldNotParseUri(url)),
+    };
+
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Authorization", auth.to_header())
+        .header("Content-Type", "application/json")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(translator.client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::YandexCloudAPIError(YandexCloudError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a Yandex Cloud translate response
+#[derive(Debug, Serialize, Deserialize)]
+struct TranslateResponse {
+    translations: Vec<Translation>,
+}
+
+/// Text translation in a TranslateResponse
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Translation {
+    text: String,
+    #[serde(default)]
+    detected_language_code: Option<String>,
+}
+
+/// Serializable struct of a Yandex Cloud detect response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DetectResponse {
+    language_code: String,
+}
+
+impl ApiDetectResponse for DetectResponse {
+    fn get_lang(&self) -> Option<Language> {
+        Language::from_language_code(&self.language_code)
+    }
+}
+
+/// Serializable struct of a Yandex Cloud languages response
+#[derive(Debug, Serialize, Deserialize)]
+struct LanguagesResponse {
+    languages: Vec<LanguageInfo>,
+}
+
+/// Language listed in a LanguagesResponse
+#[derive(Debug, Serialize, Deserialize)]
+struct LanguageInfo {
+    code: String,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// Serializable struct of a Yandex Cloud error response
+#[derive(Debug, Serialize, Deserialize)]
+struct ErrorResponse {
+    code: u16,
+    message: String,
+}
+
+/// Enum containing different errors that may be returned by the Yandex Cloud API.
+///
+/// The body of the error responses holds a [gRPC status code](https://grpc.github.io/grpc/core/md_doc_statuscodes.html),
+/// which is used when available; otherwise the HTTP status code is used.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum YandexCloudError {
+    /// The request is invalid, e.g. an unsupported language or a missing folder ID.
+    InvalidArgument,
+    /// The API key or IAM token is missing, invalid or expired.
+    Unauthenticated,
+    /// The credentials are not allowed to use the folder.
+    PermissionDenied,
+    /// A quota or limit has been exceeded.
+    ResourceExhausted,
+    /// An unexpected error happened on the server side.
+    Internal,
+    /// The service is temporarily unavailable.
+    Unavailable,
+    UnknownErrorCode(u16),
+}
+
+impl YandexCloudError {
+    /// Converts a gRPC status code, as found in the body of the error responses, to the enum variant.
+    ///
+    /// Returns `None` if the code has no matching variant.
+    pub fn from_grpc_code(code: u16) -> Option<Self> {
+        use YandexCloudError::*;
+        match code {
+            3 => Some(InvalidArgument),
+            7 => Some(PermissionDenied),
+            8 => Some(ResourceExhausted),
+            13 => Some(Internal),
+            14 => Some(Unavailable),
+            16 => Some(Unauthenticated),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for YandexCloudError {
+    fn from_error_code(code: u16) -> Self {
+        use YandexCloudError::*;
+        match code {
+            400 => InvalidArgument,
+            401 => Unauthenticated,
+            403 => PermissionDenied,
+            429 => ResourceExhausted,
+            500 => Internal,
+            503 => Unavailable,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use YandexCloudError::*;
+        match self {
+            InvalidArgument => 400,
+            Unauthenticated => 401,
+            PermissionDenied => 403,
+            ResourceExhausted => 429,
+            Internal => 500,
+            Unavailable => 503,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the gRPC `code` and the `message` of the error response.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => {
+                let kind =
+                    Self::from_grpc_code(res.code).unwrap_or_else(|| Self::from_error_code(status));
+                ProviderError::new(kind, status)
+                    .with_code(&res.code.to_string())
+                    .with_message(&res.message)
+            }
+            Err(_) => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use YandexCloudError::*;
+        match self {
+            ResourceExhausted | Internal | Unavailable => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use YandexCloudError::*;
+        match self {
+            Unauthenticated | PermissionDenied => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use YandexCloudError::*;
+        match self {
+            ResourceExhausted => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    /// Yandex Cloud reports the unsupported languages as invalid arguments, without telling them apart.
+    fn is_unsupported_pair(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for YandexCloudError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Yandex Cloud error {} : {:?}",
+            self.to_error_code(),
+            self
+        )
+    }
+}
+
+impl std::error::Error for YandexCloudError {}
@@ -0,0 +1,390 @@
+/*!
+A module containing the implementation of the [Youdao Translate API](https://ai.youdao.com/DOCSIRMA/html/trans/api/wbfy/index.html).
+
+To use it, see the [`Youdao struct`](struct.Youdao.html).
+*/
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::{uri::Uri, Request};
+use hyper::{body::to_bytes, Body};
+use serde::{Deserialize, Serialize};
+use serde_json::from_str;
+use sha2::{Digest, Sha256};
+use urlencoding::encode;
+
+use super::*;
+
+/// Base URL used to access the Youdao API.
+pub const YOUDAO_BASE_URL: &str = "https://openapi.youdao.com";
+
+/// Returns the v3 signature of a request: the SHA-256 digest of the app key, the truncated text,
+/// the salt, the current time in seconds and the app secret.
+///
+/// Texts longer than 20 characters are truncated to their first 10 characters, their length,
+/// and their last 10 characters. The text must not be URL-encoded.
+pub fn sign(app_key: &str, text: &str, salt: &str, curtime: &str, app_secret: &str) -> String {
+    let length = text.chars().count();
+    let input = if length > 20 {
+        let first: String = text.chars().take(10).collect();
+        let last: String = text.chars().skip(length - 10).collect();
+        format!("{}{}{}", first, length, last)
+    } else {
+        text.to_string()
+    };
+
+    hex::encode(Sha256::digest(
+        format!("{}{}{}{}{}", app_key, input, salt, curtime, app_secret).as_bytes(),
+    ))
+}
+
+/// # Youdao Translate API
+///
+/// A struct representing the [Youdao Translate API](https://ai.youdao.com/DOCSIRMA/html/trans/api/wbfy/index.html) (有道智云).
+///
+/// Each request is signed with the v3 scheme, a SHA-256 digest of the app key, the text, a random salt,
+/// the current time and the app secret.
+///
+/// It implements:
+///
+/// - language translation, with the default [`Api`](../trait.Api.html) trait
+///
+/// ### Text translation
+///
+/// Translate a text from an unknown language to Chinese:
+///
+/// ```no_run
+/// use text_translator::*;
+///
+/// # async fn run() -> Result<(), Error> {
+/// // construct the struct
+/// let translator = Youdao::with_credentials("<YOUDAO_APP_KEY>", "<YOUDAO_APP_SECRET>");
+///
+/// let text: String = "Hello, my name is Naruto Uzumaki!".to_string();
+///
+/// // translate the text, returns a `Result<String, Error>`
+/// let translated_text: String = translator
+///     .translate(text, InputLanguage::Automatic, Language::Chinese)
+///     .await?;
+///
+/// assert_eq!(translated_text, "你好，我叫漩涡鸣人！");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub struct Youdao<'a> {
+    credentials: Option<(&'a str, &'a str)>,
+    base_url: &'a str,
+    client: Option<&'a HttpClient>,
+}
+
+impl<'a> Youdao<'a> {
+    /// Returns a new [`Youdao`](struct.Youdao.html) struct with the given app key and app secret.
+    ///
+    /// Can be used in constant definitions.
+    pub const fn with_credentials(app_key: &'a str, app_secret: &'a str) -> Self {
+        Self {
+            credentials: Some((app_key, app_secret)),
+            base_url: YOUDAO_BASE_URL,
+            client: None,
+        }
+    }
+
+    /// Sends the requests to the given URL instead of [`YOUDAO_BASE_URL`](constant.YOUDAO_BASE_URL.html).
+    pub const fn with_base_url(mut self, base_url: &'a str) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Sends the requests with the given [`HttpClient`](../struct.HttpClient.html), reusing its pooled connections.
+    ///
+    /// By default, a new connection is opened for each request.
+    pub const fn with_client(mut self, client: &'a HttpClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Returns the app key used by the requests, if set.
+    pub fn get_app_key(&self) -> Option<&'a str> {
+        self.credentials.map(|(app_key, _)| app_key)
+    }
+
+    /// Sends a translation request, from a source language code which may be `auto`.
+    async fn send_translate(
+        &self,
+        text: &str,
+        source_language: &str,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        let (app_key, app_secret) = self.credentials.ok_or(Error::NoApiKeySet)?;
+
+        // build query
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let salt = now.as_nanos().to_string();
+        let curtime = now.as_secs().to_string();
+        let url = format!("{}/api", self.base_url);
+        let body = format!(
+            "q={}&from={}&to={}&appKey={}&salt={}&sign={}&signType=v3&curtime={}",
+            encode(text),
+            source_language,
+            language_code(target_language),
+            encode(app_key),
+            salt,
+            sign(app_key, text, &salt, &curtime, app_secret),
+            curtime
+        );
+
+        let uri = match url.parse::<Uri>() {
+            Ok(res) => res,
+            Err(_) => return Err(Error::CouldNotParseUri(url)),
+        };
+
+        let body = get_response(self.client, uri, body).await?;
+
+        let json_body: TranslateResponse = match from_str(body.as_str()) {
+            Ok(res) => res,
+            Err(err) => return Err(Error::InvalidResponse(ErrorSource::new(err))),
+        };
+
+        // errors are returned with a successful HTTP status
+        if json_body.error_code != "0" {
+            return Err(Error::YoudaoAPIError(YoudaoError::from_response(
+                200, &body,
+            )));
+        }
+
+        Ok(json_body.get_text())
+    }
+}
+
+#[async_trait]
+impl<'a> Api for Youdao<'a> {
+    /// Returns a new [`Youdao`](struct.Youdao.html) struct without credentials.
+    fn new() -> Self {
+        Self {
+            credentials: None,
+            base_url: YOUDAO_BASE_URL,
+            client: None,
+        }
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        source_language: InputLanguage,
+        target_language: Language,
+    ) -> Result<String, Error> {
+        self.credentials.ok_or(Error::NoApiKeySet)?;
+
+        // get translation direction
+        let source_language = match source_language {
+            InputLanguage::Automatic => "auto",
+            InputLanguage::Defined(source) => {
+                // verify that source languages != target language
+                if source == target_language {
+                    return Err(Error::SameLanguages(source, target_language));
+                }
+
+                language_code(source)
+            }
+        };
+
+        retry::run(self.client, || {
+            self.send_translate(&text, source_language, target_language)
+        })
+        .await
+    }
+}
+
+/// Returns the code used by the API for a language.
+fn language_code(language: Language) -> &'static str {
+    match language {
+        Language::Chinese => "zh-CHS",
+        other => other.to_language_code(),
+    }
+}
+
+/// Returns the response json body, needed to be deserialized.
+async fn get_response(
+    client: Option<&HttpClient>,
+    uri: Uri,
+    body: String,
+) -> Result<String, Error> {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body(Body::from(body))
+        .map_err(|e| Error::RequestError(e.to_string()))?;
+
+    let res = client::send(client, req).await?;
+    let status = res.status().as_u16();
+
+    let body = to_bytes(res.into_body())
+        .await
+        .map_err(|e| Error::TransportError(ErrorSource::new(e)))?;
+    let body = match std::str::from_utf8(&body) {
+        Ok(res) => res.to_string(),
+        Err(err) => return Err(Error::CouldNotConvertToUtf8Str(err)),
+    };
+
+    match status {
+        200 => Ok(body),
+        status => Err(Error::YoudaoAPIError(YoudaoError::from_response(
+            status, &body,
+        ))),
+    }
+}
+
+/// Serializable struct of a Youdao translate response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TranslateResponse {
+    error_code: String,
+    #[serde(default)]
+    translation: Vec<String>,
+}
+
+impl ApiTranslateResponse for TranslateResponse {
+    fn get_text(&self) -> String {
+        self.translation.concat()
+    }
+}
+
+/// Serializable struct of a Youdao error response
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorResponse {
+    error_code: String,
+}
+
+/// Enum containing different errors that may be returned by the Youdao API.
+#[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq)]
+pub enum YoudaoError {
+    /// A required parameter is missing.
+    MissingParameter,
+    /// The language is not supported.
+    UnsupportedLanguage,
+    /// The text sent is too long.
+    TextTooLong,
+    /// The app key is invalid.
+    InvalidAppKey,
+    /// The app has no translation instance bound.
+    NoInstance,
+    /// The developer account is invalid.
+    InvalidAccount,
+    /// The signature is invalid, e.g. a wrong app secret or a clock skew.
+    InvalidSignature,
+    /// The account is overdue.
+    AccountOverdue,
+    /// Too many requests were sent, the request should be retried later.
+    AccessFrequencyLimited,
+    /// An error code missing from this enum was returned.
+    UnknownYoudaoCode,
+    UnknownErrorCode(u16),
+}
+
+impl YoudaoError {
+    /// Converts an error code, as found in the `errorCode` field of the responses, to the enum variant.
+    ///
+    /// Returns `None` if the code has no matching variant.
+    pub fn from_youdao_code(code: &str) -> Option<Self> {
+        use YoudaoError::*;
+        match code {
+            "101" | "113" => Some(MissingParameter),
+            "102" => Some(UnsupportedLanguage),
+            "103" => Some(TextTooLong),
+            "108" => Some(InvalidAppKey),
+            "110" => Some(NoInstance),
+            "111" => Some(InvalidAccount),
+            "202" => Some(InvalidSignature),
+            "401" => Some(AccountOverdue),
+            "411" | "412" => Some(AccessFrequencyLimited),
+            _ => None,
+        }
+    }
+}
+
+impl ApiError for YoudaoError {
+    fn from_error_code(code: u16) -> Self {
+        use YoudaoError::*;
+        match code {
+            400 => MissingParameter,
+            401 => InvalidAppKey,
+            429 => AccessFrequencyLimited,
+            other => UnknownErrorCode(other),
+        }
+    }
+
+    fn to_error_code(&self) -> u16 {
+        use YoudaoError::*;
+        match self {
+            MissingParameter | UnsupportedLanguage | TextTooLong => 400,
+            InvalidAppKey | InvalidAccount | InvalidSignature => 401,
+            AccountOverdue => 402,
+            NoInstance => 403,
+            AccessFrequencyLimited => 429,
+            UnknownYoudaoCode => 500,
+            UnknownErrorCode(other) => *other,
+        }
+    }
+
+    /// Parses the `errorCode` of the response, which usually has a `200` status code.
+    ///
+    /// The API returns no error message.
+    fn from_response(status: u16, body: &str) -> ProviderError<Self> {
+        match from_str::<ErrorResponse>(body) {
+            Ok(res) => {
+                let kind =
+                    Self::from_youdao_code(&res.error_code).unwrap_or_else(|| match status {
+                        200 => YoudaoError::UnknownYoudaoCode,
+                        status => Self::from_error_code(status),
+                    });
+                ProviderError::new(kind, status).with_code(&res.error_code)
+            }
+            Err(_) => ProviderError::new(Self::from_error_code(status), status),
+        }
+    }
+
+    fn is_retryable(&self) -> bool {
+        use YoudaoError::*;
+        match self {
+            AccessFrequencyLimited => true,
+            UnknownErrorCode(status) => is_retryable_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_auth(&self) -> bool {
+        use YoudaoError::*;
+        match self {
+            InvalidAppKey | NoInstance | InvalidAccount | InvalidSignature => true,
+            UnknownErrorCode(status) => is_auth_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_quota(&self) -> bool {
+        use YoudaoError::*;
+        match self {
+            AccessFrequencyLimited | AccountOverdue => true,
+            UnknownErrorCode(status) => is_quota_status(*status),
+            _ => false,
+        }
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        use YoudaoError::*;
+        matches!(self, UnsupportedLanguage)
+    }
+}
+
+impl std::fmt::Display for YoudaoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Youdao error {} : {:?}", self.to_error_code(), self)
+    }
+}
+
+impl std::error::Error for YoudaoError {}
@@ -212,6 +212,108 @@ impl Language {
         }
     }
 
+    /// Returns the English name of the language, e.g. `"Haitian Creole"`.
+    ///
+    /// Useful to describe a language in natural text, such as a prompt.
+    pub fn to_language_name(&self) -> &'static str {
+        use Language::*;
+        match *self {
+            Afrikaans => "Afrikaans",
+            Albanian => "Albanian",
+            Amharic => "Amharic",
+            Arabic => "Arabic",
+            Armenian => "Armenian",
+            Azerbaijan => "Azerbaijani",
+            Bashkir => "Bashkir",
+            Basque => "Basque",
+            Belarusian => "Belarusian",
+            Bengali => "Bengali",
+            Bosnian => "Bosnian",
+            Bulgarian => "Bulgarian",
+            Burmese => "Burmese",
+            Catalan => "Catalan",
+            Cebuano => "Cebuano",
+            Chinese => "Chinese",
+            Croatian => "Croatian",
+            Czech => "Czech",
+            Danish => "Danish",
+            Dutch => "Dutch",
+            English => "English",
+            Esperanto => "Esperanto",
+            Estonian => "Estonian",
+            Finnish => "Finnish",
+            French => "French",
+            Galician => "Galician",
+            Georgian => "Georgian",
+            German => "German",
+            Greek => "Greek",
+            Gujarati => "Gujarati",
+            HaitianCreole => "Haitian Creole",
+            Hebrew => "Hebrew",
+            HillMari => "Hill Mari",
+            Hindi => "Hindi",
+            Hungarian => "Hungarian",
+            Icelandic => "Icelandic",
+            Indonesian => "Indonesian",
+            Irish => "Irish",
+            Italian => "Italian",
+            Japanese => "Japanese",
+            Javanese => "Javanese",
+            Kannada => "Kannada",
+            Kazakh => "Kazakh",
+            Khmer => "Khmer",
+            Korean => "Korean",
+            Kyrgyz => "Kyrgyz",
+            Laotian => "Lao",
+            Latin => "Latin",
+            Latvian => "Latvian",
+            Lithuanian => "Lithuanian",
+            Luxembourgish => "Luxembourgish",
+            Macedonian => "Macedonian",
+            Malagasy => "Malagasy",
+            Malay => "Malay",
+            Malayalam => "Malayalam",
+            Maltese => "Maltese",
+            Maori => "Maori",
+            Marathi => "Marathi",
+            Mari => "Mari",
+            Mongolian => "Mongolian",
+            Nepali => "Nepali",
+            Norwegian => "Norwegian",
+            Papiamento => "Papiamento",
+            Persian => "Persian",
+            Polish => "Polish",
+            Portuguese => "Portuguese",
+            Punjabi => "Punjabi",
+            Romanian => "Romanian",
+            Russian => "Russian",
+            Scottish => "Scottish Gaelic",
+            Serbian => "Serbian",
+            Sinhala => "Sinhala",
+            Slovakian => "Slovak",
+            Slovenian => "Slovenian",
+            Spanish => "Spanish",
+            Sundanese => "Sundanese",
+            Swahili => "Swahili",
+            Swedish => "Swedish",
+            Tagalog => "Tagalog",
+            Tajik => "Tajik",
+            Tamil => "Tamil",
+            Tatar => "Tatar",
+            Telugu => "Telugu",
+            Thai => "Thai",
+            Turkish => "Turkish",
+            Udmurt => "Udmurt",
+            Ukrainian => "Ukrainian",
+            Urdu => "Urdu",
+            Uzbek => "Uzbek",
+            Vietnamese => "Vietnamese",
+            Welsh => "Welsh",
+            Xhosa => "Xhosa",
+            Yiddish => "Yiddish",
+        }
+    }
+
     pub fn from_language_code(code: &str) -> Option<Language> {
         use Language::*;
         match code {
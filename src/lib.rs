@@ -13,10 +13,26 @@ This crate permits to translate text between languages easily. Its goals are:
 It wants to implement the following APIs:
 
 - `[x]` [Yandex.Translate](https://tech.yandex.com/translate/doc/dg/concepts/about-docpage)
-    - `[x]` with [API key](https://translate.yandex.com/developers/keys)
-    - `[ ]` without key (5_000 chars/translation max)
-- `[ ]` [Google Translate](https://cloud.google.com/translate/docs/)
-- `[ ]` [Bing](https://azure.microsoft.com/en-us/services/cognitive-services/translator-text-api/)
+    - `[x]` with [API key](https://translate.yandex.com/developers/keys) (deprecated, the v1.5 API has been shut down)
+    - `[-]` without key, out of scope: the web endpoint needs a session id scraped from the Yandex pages, use `GoogleWeb` instead
+- `[x]` [Yandex Cloud Translate](https://cloud.yandex.com/en/docs/translate/), with API key or IAM token
+- `[x]` [Google Translate](https://cloud.google.com/translate/docs/)
+    - `[x]` v2 (Basic), with API key
+    - `[x]` [v3 (Advanced)](https://cloud.google.com/translate/docs/advanced/translating-text-v3), with service account
+    - `[x]` without key, with the `keyless` feature (5_000 chars/request, longer texts are split)
+- `[x]` [DeepL](https://www.deepl.com/docs-api)
+- `[x]` [Bing / Microsoft Translator](https://azure.microsoft.com/en-us/services/cognitive-services/translator-text-api/)
+- `[x]` [LibreTranslate](https://libretranslate.com/docs), with or without API key
+- `[x]` [Amazon Translate](https://docs.aws.amazon.com/translate/latest/APIReference/welcome.html), with AWS credentials
+- `[x]` [MyMemory](https://mymemory.translated.net/doc/spec.php), with or without key
+- `[x]` [Lingva Translate](https://github.com/thedaviddelta/lingva-translate), without key
+- `[x]` [Apertium](https://wiki.apertium.org/wiki/Apertium-apy), with a local or remote APy server
+- `[x]` [Papago](https://api.ncloud-docs.com/docs/en/ai-naver-papagonmt-translation), with client ID and secret
+- `[x]` [Baidu Translate](https://fanyi-api.baidu.com/doc/21), with app ID and secret key
+- `[x]` [Youdao Translate](https://ai.youdao.com/DOCSIRMA/html/trans/api/wbfy/index.html), with app key and secret
+- `[x]` [ModernMT](https://www.modernmt.com/api/), with API key, context vectors and memories
+- `[x]` LLMs through an [OpenAI-compatible API](https://platform.openai.com/docs/api-reference/chat), e.g. llama.cpp or vLLM
+- `[x]` offline, with [Argos Translate](https://github.com/argosopentech/argos-translate) packages run with CTranslate2 and the `offline` feature (building it needs CMake and a C++17 compiler)
 
 ## How to use
 
@@ -30,11 +46,41 @@ Then, you will be able to do various function calls on this struct:
 Languages are represented with the [`Language`](enum.Language.html) enum for target language, and [`InputLanguage`](enum.InputLanguage.html) for input language.
 See their respective documentations for more.
 
-## Examples
+By default, each request opens a new connection. To reuse connections between requests, give the translators
+a shared [`HttpClient`](struct.HttpClient.html) with their `with_client` method.
+The client sends the requests through a [`Transport`](trait.Transport.html), which can be replaced to use another
+HTTP stack, or to answer the requests in memory in tests.
+The client can also retry the requests failing with a transient error, e.g. a rate limiting, following a
+[`RetryPolicy`](struct.RetryPolicy.html) set with its `with_retry` method.
+The requests time out after the [`Timeouts`](struct.Timeouts.html) of the client, which can be set with `HttpClient::with_timeouts`;
+dropping the future of a request cancels it.
+The requests go through the proxy given by the `HTTPS_PROXY` environment variable, if set, or through the
+[`Proxy`](struct.Proxy.html) set with `HyperTransport::with_proxy`, and `HyperTransport::with_root_certificates`
+trusts additional root certificates, e.g. the one of a corporate proxy.
+The TLS stack is selected with one of two mutually exclusive features: `native-tls` (default), using the library
+of the system such as OpenSSL, or `rustls` (with `default-features = false`), which needs no system library, e.g. for
+static musl binaries. With `rustls`, the webpki root certificates are trusted, or the ones of the system with the
+`rustls-native-roots` feature. Enabling both, e.g. with `--all-features`, fails the build.
+
+The translators send their requests to the public endpoint of their API by default. Their `with_base_url` method
+(`with_endpoint` for Amazon) sends them elsewhere instead, e.g. to a regional endpoint, an egress proxy or a local mock server.
+
+To stay under the quotas of an API, any translator can be wrapped in a [`RateLimited`](struct.RateLimited.html) struct,
+limiting the requests and characters it sends per second, and counting its usage.
+
+Several texts can be translated at once with `translate_batch`, returning the translations in order:
+`GoogleV2` and `Yandex` send them in as few requests as their limits allow, the other engines one by one.
 
-For the moment, only the Yandex API is implemented.
+The errors returned by an API are wrapped in a [`ProviderError`](struct.ProviderError.html), holding the error variant of the engine
+parsed from the response, the HTTP status code, and the error code and message of the provider when it sent them.
+No request panics: the transport, deserialization and provider failures are returned as an [`Error`](enum.Error.html),
+whose `source()` is the underlying error.
+To handle them without matching on every variant, `is_retryable`, `is_auth`, `is_quota` and `is_unsupported_pair`
+classify them the same way for every engine, e.g. to retry a request or to fail over to another engine.
 
-To see examples on how to use it, see [its documentation](struct.Yandex.html).
+## Examples
+
+To see examples on how to use an API, see the documentation of its struct, e.g. [`YandexCloud`](struct.YandexCloud.html).
 */
 
 mod api;
@@ -56,6 +102,10 @@ pub enum Error {
     CouldNotSerializeJson,
     /// Error when sending API request : no KEY set.
     NoApiKeySet,
+    /// Error loading or using the credentials, e.g. an unreadable key file.
+    InvalidCredentials(String),
+    /// The credentials could not be parsed, e.g. a malformed service account key file.
+    CouldNotParseCredentials(ErrorSource),
     /// Error parsing query to a valid URI.
     CouldNotParseUri(String),
     /// Error executing `tokio::runtime::Runtime::new()`.
@@ -65,17 +115,302 @@ pub enum Error {
     /// Could not retrieve language code.
     UnknownLanguageCode(String),
     /// Yandex API error.
-    YandexAPIError(api::yandex::YandexError),
+    YandexAPIError(ProviderError<api::yandex::YandexError>),
+    /// Yandex Cloud API error.
+    YandexCloudAPIError(ProviderError<api::yandex_cloud::YandexCloudError>),
     /// Google API error
-    GoogleV2APIError(api::google_v2::GoogleV2Error),
+    GoogleV2APIError(ProviderError<api::google_v2::GoogleV2Error>),
+    /// Google v3 API error.
+    GoogleV3APIError(ProviderError<api::google_v3::GoogleV3Error>),
+    /// Keyless Google endpoint error.
+    #[cfg(feature = "keyless")]
+    GoogleWebAPIError(ProviderError<api::google_web::GoogleWebError>),
+    /// DeepL API error.
+    DeepLAPIError(ProviderError<api::deepl::DeepLError>),
+    /// Microsoft Translator API error.
+    AzureAPIError(ProviderError<api::azure::AzureError>),
+    /// LibreTranslate API error.
+    LibreTranslateAPIError(ProviderError<api::libretranslate::LibreTranslateError>),
+    /// Amazon Translate API error.
+    AmazonAPIError(ProviderError<api::amazon::AmazonError>),
+    /// MyMemory API error.
+    MyMemoryAPIError(ProviderError<api::mymemory::MyMemoryError>),
+    /// Lingva Translate API error.
+    LingvaAPIError(ProviderError<api::lingva::LingvaError>),
+    /// Apertium APy API error.
+    ApertiumAPIError(ProviderError<api::apertium::ApertiumError>),
+    /// Papago API error.
+    PapagoAPIError(ProviderError<api::papago::PapagoError>),
+    /// Baidu API error.
+    BaiduAPIError(ProviderError<api::baidu::BaiduError>),
+    /// Youdao API error.
+    YoudaoAPIError(ProviderError<api::youdao::YoudaoError>),
+    /// ModernMT API error.
+    ModernMtAPIError(ProviderError<api::modernmt::ModernMtError>),
+    /// OpenAI-compatible LLM API error.
+    LlmAPIError(ProviderError<api::llm::LlmError>),
+    /// Offline engine error.
+    #[cfg(feature = "offline")]
+    OfflineError(api::offline::OfflineError),
     /// Error in request or response
-    RequestError(String)
+    RequestError(String),
+    /// The request could not be sent, or its response could not be read, e.g. a DNS failure or a refused connection.
+    TransportError(ErrorSource),
+    /// The response of the API could not be deserialized, e.g. an HTML page instead of a JSON document.
+    InvalidResponse(ErrorSource),
+    /// The request timed out, see [`Timeouts`](struct.Timeouts.html).
+    Timeout,
+    /// Invalid TLS configuration, e.g. an unreadable root certificate.
+    TlsError(String),
+    /// Some texts of a batch could not be translated, see [`Api::translate_batch`](trait.Api.html#method.translate_batch):
+    /// holds the result of each text, in order. It is classified by its failures, e.g. retryable if all of them are.
+    BatchError(Vec<Result<String, Error>>),
 }
 
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error : {}", &self)
+        use Error::*;
+        match self {
+            CouldNotConvertToUtf8String(_) | CouldNotConvertToUtf8Str(_) => {
+                write!(f, "the response is not valid UTF-8")
+            }
+            CouldNotDerializeJson => write!(f, "unexpected JSON response"),
+            CouldNotSerializeJson => write!(f, "could not serialize the JSON request"),
+            NoApiKeySet => write!(f, "no API key set"),
+            InvalidCredentials(message) => write!(f, "invalid credentials: {}", message),
+            CouldNotParseCredentials(_) => write!(f, "could not parse the credentials"),
+            CouldNotParseUri(uri) => write!(f, "could not parse the URI {}", uri),
+            FailedToCreateTokioRuntime => write!(f, "could not create the tokio runtime"),
+            SameLanguages(source, target) => write!(
+                f,
+                "the source and target languages are the same: {:?} and {:?}",
+                source, target
+            ),
+            UnknownLanguageCode(code) => write!(f, "unknown language code {}", code),
+            YandexAPIError(_) => write!(f, "Yandex API error"),
+            YandexCloudAPIError(_) => write!(f, "Yandex Cloud API error"),
+            GoogleV2APIError(_) => write!(f, "Google v2 API error"),
+            GoogleV3APIError(_) => write!(f, "Google v3 API error"),
+            #[cfg(feature = "keyless")]
+            GoogleWebAPIError(_) => write!(f, "keyless Google endpoint error"),
+            DeepLAPIError(_) => write!(f, "DeepL API error"),
+            AzureAPIError(_) => write!(f, "Microsoft Translator API error"),
+            LibreTranslateAPIError(_) => write!(f, "LibreTranslate API error"),
+            AmazonAPIError(_) => write!(f, "Amazon Translate API error"),
+            MyMemoryAPIError(_) => write!(f, "MyMemory API error"),
+            LingvaAPIError(_) => write!(f, "Lingva Translate API error"),
+            ApertiumAPIError(_) => write!(f, "Apertium APy API error"),
+            PapagoAPIError(_) => write!(f, "Papago API error"),
+            BaiduAPIError(_) => write!(f, "Baidu API error"),
+            YoudaoAPIError(_) => write!(f, "Youdao API error"),
+            ModernMtAPIError(_) => write!(f, "ModernMT API error"),
+            LlmAPIError(_) => write!(f, "LLM API error"),
+            #[cfg(feature = "offline")]
+            OfflineError(_) => write!(f, "offline engine error"),
+            RequestError(message) => write!(f, "request error: {}", message),
+            TransportError(_) => write!(f, "could not send the request or read its response"),
+            InvalidResponse(_) => write!(f, "could not deserialize the response"),
+            Timeout => write!(f, "the request timed out"),
+            TlsError(message) => write!(f, "invalid TLS configuration: {}", message),
+            BatchError(results) => write!(
+                f,
+                "{} of {} texts could not be translated",
+                batch_errors(results).count(),
+                results.len()
+            ),
+        }
+    }
+}
+
+impl Error {
+    /// Returns whether the error is transient, the same request being likely to succeed later:
+    /// a transport failure, a timeout, or a retryable error of the API, e.g. a rate limiting.
+    ///
+    /// The [`RetryPolicy`](struct.RetryPolicy.html) retries the transport errors it classifies this way by default.
+    pub fn is_retryable(&self) -> bool {
+        use Error::*;
+        match self {
+            TransportError(_) | Timeout => true,
+            BatchError(results) => batch_errors(results).all(Error::is_retryable),
+            _ => self.provider_error().is_some_and(Classify::is_retryable),
+        }
+    }
+
+    /// Returns whether the credentials are missing, invalid, or not allowed to use the API: a new key is needed.
+    pub fn is_auth(&self) -> bool {
+        use Error::*;
+        match self {
+            NoApiKeySet | InvalidCredentials(_) | CouldNotParseCredentials(_) => true,
+            BatchError(results) => batch_errors(results).all(Error::is_auth),
+            _ => self.provider_error().is_some_and(Classify::is_auth),
+        }
+    }
+
+    /// Returns whether a usage limit of the account has been reached: either a rate limit, also retryable,
+    /// or a quota, which is not until it is reset or raised.
+    pub fn is_quota(&self) -> bool {
+        match self {
+            Error::BatchError(results) => batch_errors(results).all(Error::is_quota),
+            _ => self.provider_error().is_some_and(Classify::is_quota),
+        }
+    }
+
+    /// Returns whether the pair of languages, or one of them, is not supported by the engine,
+    /// e.g. an automatic detection with an engine unable to detect the source language.
+    pub fn is_unsupported_pair(&self) -> bool {
+        match self {
+            #[cfg(feature = "offline")]
+            Error::OfflineError(api::offline::OfflineError::UnsupportedPair(_, _))
+            | Error::OfflineError(api::offline::OfflineError::DetectionUnsupported) => true,
+            Error::BatchError(results) => batch_errors(results).all(Error::is_unsupported_pair),
+            _ => self
+                .provider_error()
+                .is_some_and(Classify::is_unsupported_pair),
+        }
+    }
+
+    /// Returns the HTTP status code of the response reporting the error of the API, if any.
+    pub(crate) fn provider_status(&self) -> Option<u16> {
+        self.provider_error().map(Classify::status)
+    }
+
+    /// Returns the error returned by the API, if any.
+    fn provider_error(&self) -> Option<&dyn Classify> {
+        use Error::*;
+        match self {
+            YandexAPIError(err) => Some(err),
+            YandexCloudAPIError(err) => Some(err),
+            GoogleV2APIError(err) => Some(err),
+            GoogleV3APIError(err) => Some(err),
+            #[cfg(feature = "keyless")]
+            GoogleWebAPIError(err) => Some(err),
+            DeepLAPIError(err) => Some(err),
+            AzureAPIError(err) => Some(err),
+            LibreTranslateAPIError(err) => Some(err),
+            AmazonAPIError(err) => Some(err),
+            MyMemoryAPIError(err) => Some(err),
+            LingvaAPIError(err) => Some(err),
+            ApertiumAPIError(err) => Some(err),
+            PapagoAPIError(err) => Some(err),
+            BaiduAPIError(err) => Some(err),
+            YoudaoAPIError(err) => Some(err),
+            ModernMtAPIError(err) => Some(err),
+            LlmAPIError(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the errors of the texts of a batch that could not be translated.
+fn batch_errors(results: &[Result<String, Error>]) -> impl Iterator<Item = &Error> {
+    results.iter().filter_map(|result| result.as_ref().err())
+}
+
+/// The classification of an API error, whatever its engine.
+trait Classify {
+    fn is_retryable(&self) -> bool;
+    fn is_auth(&self) -> bool;
+    fn is_quota(&self) -> bool;
+    fn is_unsupported_pair(&self) -> bool;
+    fn status(&self) -> u16;
+}
+
+impl<E: ApiError> Classify for ProviderError<E> {
+    fn is_retryable(&self) -> bool {
+        ProviderError::is_retryable(self)
+    }
+
+    fn is_auth(&self) -> bool {
+        ProviderError::is_auth(self)
+    }
+
+    fn is_quota(&self) -> bool {
+        ProviderError::is_quota(self)
+    }
+
+    fn is_unsupported_pair(&self) -> bool {
+        ProviderError::is_unsupported_pair(self)
+    }
+
+    fn status(&self) -> u16 {
+        self.get_status()
+    }
+}
+
+impl std::error::Error for Error {
+    /// Returns the underlying error: the transport or decoding error, or the error returned by the API.
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        use Error::*;
+        match self {
+            CouldNotConvertToUtf8String(err) => Some(err),
+            CouldNotConvertToUtf8Str(err) => Some(err),
+            YandexAPIError(err) => Some(err),
+            YandexCloudAPIError(err) => Some(err),
+            GoogleV2APIError(err) => Some(err),
+            GoogleV3APIError(err) => Some(err),
+            #[cfg(feature = "keyless")]
+            GoogleWebAPIError(err) => Some(err),
+            DeepLAPIError(err) => Some(err),
+            AzureAPIError(err) => Some(err),
+            LibreTranslateAPIError(err) => Some(err),
+            AmazonAPIError(err) => Some(err),
+            MyMemoryAPIError(err) => Some(err),
+            LingvaAPIError(err) => Some(err),
+            ApertiumAPIError(err) => Some(err),
+            PapagoAPIError(err) => Some(err),
+            BaiduAPIError(err) => Some(err),
+            YoudaoAPIError(err) => Some(err),
+            ModernMtAPIError(err) => Some(err),
+            LlmAPIError(err) => Some(err),
+            #[cfg(feature = "offline")]
+            OfflineError(err) => Some(err),
+            TransportError(err) | InvalidResponse(err) | CouldNotParseCredentials(err) => {
+                Some(err.get())
+            }
+            BatchError(results) => batch_errors(results)
+                .next()
+                .map(|err| err as &(dyn std::error::Error + 'static)),
+            _ => None,
+        }
+    }
+}
+
+/// # Error source
+///
+/// The underlying error of an [`Error`](enum.Error.html), e.g. the `hyper` or `serde_json` one, returned by its `source` method.
+///
+/// It is shared, so that the errors can still be cloned, and two sources are equal when their messages are.
+#[derive(Clone)]
+pub struct ErrorSource(std::sync::Arc<dyn std::error::Error + Send + Sync>);
+
+impl ErrorSource {
+    /// Returns a new source wrapping the given error.
+    pub fn new<E: std::error::Error + Send + Sync + 'static>(error: E) -> Self {
+        Self(std::sync::Arc::new(error))
+    }
+
+    /// Returns the wrapped error, which can be downcast to its concrete type.
+    pub fn get(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self.0.as_ref()
+    }
+}
+
+impl std::fmt::Debug for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl std::fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
     }
 }
 
-impl std::error::Error for Error {}
+impl Eq for ErrorSource {}
@@ -0,0 +1,216 @@
+mod common;
+
+use common::{json, MockServer, Recorded};
+use text_translator::{amazon::*, *};
+
+fn credentials() -> AwsCredentials {
+    AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY")
+}
+
+/// Recomputes the signature of a recorded request, from the headers it was sent with.
+fn expected_authorization(req: &Recorded, credentials: &AwsCredentials) -> String {
+    let mut names = vec!["content-type", "host", "x-amz-date", "x-amz-target"];
+    if req.header("x-amz-security-token").is_some() {
+        names.push("x-amz-security-token");
+    }
+    let headers: Vec<(&str, &str)> = names
+        .iter()
+        .map(|name| (*name, req.header(name).unwrap()))
+        .collect();
+
+    credentials
+        .sign(
+            &SignableRequest {
+                method: &req.method,
+                path: &req.uri,
+                query: "",
+                headers: &headers,
+                payload: req.body.as_bytes(),
+            },
+            "eu-west-1",
+            "translate",
+            req.header("x-amz-date").unwrap(),
+        )
+        .unwrap()
+}
+
+#[test]
+fn amazon_signature_v4_test_suite() {
+    // `get-vanilla` case of the AWS Signature Version 4 test suite
+    let authorization = credentials()
+        .sign(
+            &SignableRequest {
+                method: "GET",
+                path: "/",
+                query: "",
+                headers: &[
+                    ("Host", "example.amazonaws.com"),
+                    ("X-Amz-Date", "20150830T123600Z"),
+                ],
+                payload: b"",
+            },
+            "us-east-1",
+            "service",
+            "20150830T123600Z",
+        )
+        .unwrap();
+
+    assert_eq!(
+        authorization,
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+         SignedHeaders=host;x-amz-date, \
+         Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+    );
+}
+
+#[tokio::test]
+async fn amazon_translate_signed() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"TranslatedText":"Bonjour le monde","SourceLanguageCode":"en","TargetLanguageCode":"fr"}"#,
+        )
+    })
+    .await;
+    let credentials = credentials().with_session_token("session-token");
+    let translator = AmazonTranslate::with_credentials(credentials.clone())
+        .with_region("eu-west-1")
+        .with_endpoint(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello world".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("Bonjour le monde".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    let req = &requests[0];
+    assert_eq!(req.method, "POST");
+    assert_eq!(req.uri, "/");
+    assert_eq!(
+        req.header("x-amz-target"),
+        Some("AWSShineFrontendService_20170701.TranslateText")
+    );
+    assert_eq!(
+        req.header("content-type"),
+        Some("application/x-amz-json-1.1")
+    );
+    assert_eq!(req.header("x-amz-security-token"), Some("session-token"));
+    assert_eq!(
+        req.body,
+        r#"{"Text":"Hello world","SourceLanguageCode":"en","TargetLanguageCode":"fr"}"#
+    );
+
+    let date = req.header("x-amz-date").unwrap();
+    assert_eq!(date.len(), 16);
+    assert!(date.ends_with('Z'));
+    let authorization = req.header("authorization").unwrap();
+    assert!(authorization.starts_with(&format!(
+        "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/{}/eu-west-1/translate/aws4_request, \
+         SignedHeaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target, ",
+        &date[..8]
+    )));
+    assert_eq!(authorization, expected_authorization(req, &credentials));
+}
+
+#[tokio::test]
+async fn amazon_detect() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"TranslatedText":"Good morning","SourceLanguageCode":"zh-TW","TargetLanguageCode":"en"}"#,
+        )
+    })
+    .await;
+    let translator = AmazonTranslate::with_credentials(credentials()).with_endpoint(&server.url);
+
+    let res = translator.detect("早安".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::Chinese)));
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].body,
+        r#"{"Text":"早安","SourceLanguageCode":"auto","TargetLanguageCode":"en"}"#
+    );
+    assert!(requests[0]
+        .header("authorization")
+        .unwrap()
+        .contains("/us-east-1/translate/aws4_request"));
+}
+
+#[tokio::test]
+async fn amazon_errors() {
+    let server = MockServer::start(|req| {
+        if req.body.contains("\"de\"") {
+            json(
+                400,
+                r#"{"__type":"com.amazonaws.translate#UnsupportedLanguagePairException","Message":"Unsupported language pair: mrj to de"}"#,
+            )
+        } else if req.body.contains("\"fr\"") {
+            json(
+                403,
+                r#"{"__type":"InvalidSignatureException","message":"The request signature we calculated does not match"}"#,
+            )
+        } else {
+            json(503, "")
+        }
+    })
+    .await;
+    let translator = AmazonTranslate::with_credentials(credentials()).with_endpoint(&server.url);
+
+    let cases = [
+        (
+            Language::German,
+            ProviderError::new(AmazonError::UnsupportedLanguagePair, 400)
+                .with_code("UnsupportedLanguagePairException")
+                .with_message("Unsupported language pair: mrj to de"),
+        ),
+        (
+            Language::French,
+            ProviderError::new(AmazonError::InvalidSignature, 403)
+                .with_code("InvalidSignatureException")
+                .with_message("The request signature we calculated does not match"),
+        ),
+        (Language::Russian, AmazonError::ServiceUnavailable.into()),
+    ];
+    for (target, error) in cases.iter() {
+        let res = translator
+            .translate(
+                "Салам".to_string(),
+                InputLanguage::Defined(Language::HillMari),
+                *target,
+            )
+            .await;
+        assert_eq!(res, Err(Error::AmazonAPIError(error.clone())));
+    }
+
+    let res = AmazonTranslate::new().detect("Hello".to_string()).await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+}
+
+#[test]
+fn amazon_credentials_from_env() {
+    std::env::remove_var("AWS_ACCESS_KEY_ID");
+    match AmazonTranslate::from_env() {
+        Err(Error::InvalidCredentials(_)) => (),
+        other => panic!("expected InvalidCredentials, got {:?}", other),
+    }
+
+    std::env::set_var("AWS_ACCESS_KEY_ID", "AKIDENV");
+    std::env::set_var("AWS_SECRET_ACCESS_KEY", "secret");
+    std::env::set_var("AWS_SESSION_TOKEN", "token");
+    std::env::set_var("AWS_REGION", "ap-northeast-1");
+
+    let translator = AmazonTranslate::from_env().unwrap();
+    assert_eq!(translator.get_region(), "ap-northeast-1");
+    assert_eq!(
+        translator.get_credentials(),
+        Some(&AwsCredentials::new("AKIDENV", "secret").with_session_token("token"))
+    );
+    // secrets are not printed
+    assert!(!format!("{:?}", translator).contains("secret"));
+}
@@ -0,0 +1,125 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::{apertium::ApertiumError, *};
+
+#[tokio::test]
+async fn apertium_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"responseData":{"translatedText":"Hola, em dic Naruto!"},"responseDetails":null,"responseStatus":200}"#,
+        )
+    })
+    .await;
+    let translator = Apertium::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hola, me llamo Naruto!".to_string(),
+            InputLanguage::Defined(Language::Spanish),
+            Language::Catalan,
+        )
+        .await;
+    assert_eq!(res, Ok("Hola, em dic Naruto!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].uri,
+        "/translate?langpair=spa%7Ccat&q=Hola%2C%20me%20llamo%20Naruto%21"
+    );
+}
+
+#[tokio::test]
+async fn apertium_installed_pairs() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"responseData":[
+                {"sourceLanguage":"spa","targetLanguage":"cat"},
+                {"sourceLanguage":"spa","targetLanguage":"cat_valencia"},
+                {"sourceLanguage":"nob","targetLanguage":"nno"},
+                {"sourceLanguage":"nob","targetLanguage":"dan"},
+                {"sourceLanguage":"cat","targetLanguage":"spa"}
+            ],"responseDetails":null,"responseStatus":200}"#,
+        )
+    })
+    .await;
+    let translator = Apertium::new().with_base_url(&server.url);
+
+    let res = translator.installed_pairs().await;
+    assert_eq!(
+        res,
+        Ok(vec![
+            (Language::Spanish, Language::Catalan),
+            (Language::Norwegian, Language::Danish),
+            (Language::Catalan, Language::Spanish)
+        ])
+    );
+    assert_eq!(server.requests()[0].uri, "/listPairs");
+}
+
+#[tokio::test]
+async fn apertium_errors() {
+    let server = MockServer::start(|req| {
+        if req.uri.contains("langpair=eng%7Cjpn") {
+            json(
+                400,
+                r#"{"status":"error","code":400,"message":"Bad Request","explanation":"That pair is not installed"}"#,
+            )
+        } else {
+            json(
+                400,
+                r#"{"status":"error","code":400,"message":"Bad Request","explanation":"Missing argument q"}"#,
+            )
+        }
+    })
+    .await;
+    let translator = Apertium::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Japanese,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::ApertiumAPIError(
+            ProviderError::new(ApertiumError::PairNotInstalled, 400)
+                .with_message("That pair is not installed")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Spanish,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::ApertiumAPIError(
+            ProviderError::new(ApertiumError::BadRequest, 400).with_message("Missing argument q")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::Spanish,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::ApertiumAPIError(
+            ApertiumError::SourceLanguageRequired.into()
+        ))
+    );
+    assert_eq!(server.requests().len(), 2);
+}
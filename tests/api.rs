@@ -1,31 +1,116 @@
+#![allow(deprecated)]
+
+mod common;
+
+use common::{json, MockServer, Recorded};
+use hyper::{Body, Response};
 use text_translator::*;
 
-const YANDEX_API_KEY: &str =
-    "trnsl.1.1.20200507T202428Z.5e03932d06f63e6a.6ca69498c3b22bff94f6eda9ad8c21b4c3320078";
-const TRANSLATOR: Yandex = Yandex::with_key(YANDEX_API_KEY);
+const YANDEX_API_KEY: &str = "trnsl.1.1.20200507T202428Z.mock";
 const TEXT: &str = "Hello, my name is Naruto Uzumaki!\nI love noodles and fights.";
 
+/// Maximum number of characters accepted by the mock, as documented by the API.
+const MAX_TEXT_SIZE: usize = 10_000;
+
+/// Returns the decoded value of a parameter, from the query or else from the url-encoded body.
+fn query_param(req: &Recorded, name: &str) -> Option<String> {
+    let (_, query) = req.uri.split_once('?')?;
+
+    query
+        .split('&')
+        .chain(req.body.split('&'))
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .and_then(|(_, value)| urlencoding::decode(value).ok())
+}
+
+/// Mock of the Yandex Translate API: translations are prefixed with the target language code,
+/// which the detection then reads back.
+fn yandex_api(req: &Recorded) -> Response<Body> {
+    if query_param(req, "key").as_deref() != Some(YANDEX_API_KEY) {
+        return json(401, r#"{"code":401,"message":"API key is invalid"}"#);
+    }
+
+    let text = query_param(req, "text").unwrap_or_default();
+    if text.chars().count() > MAX_TEXT_SIZE {
+        return json(
+            413,
+            r#"{"code":413,"message":"Text size exceeds the maximum"}"#,
+        );
+    }
+
+    if req.uri.starts_with("/translate?") {
+        let lang = query_param(req, "lang").unwrap_or_default();
+        let target = lang.rsplit('-').next().unwrap_or_default();
+
+        json(
+            200,
+            &serde_json::json!({
+                "code": 200,
+                "lang": lang,
+                "text": [format!("[{}] {}", target, text)],
+            })
+            .to_string(),
+        )
+    } else if req.uri.starts_with("/detect?") {
+        let lang = text
+            .strip_prefix('[')
+            .and_then(|text| text.split(']').next())
+            .unwrap_or("en");
+
+        json(
+            200,
+            &serde_json::json!({ "code": 200, "lang": lang }).to_string(),
+        )
+    } else {
+        json(404, r#"{"code":404,"message":"Not found"}"#)
+    }
+}
+
+/// Returns a translator sending its requests to the mock server.
+fn translator(server: &MockServer) -> Yandex<'_> {
+    Yandex::with_key(YANDEX_API_KEY).with_base_url(&server.url)
+}
+
 #[tokio::test]
 async fn api_single_translate() {
-    let res = TRANSLATOR.translate(
+    let server = MockServer::start(yandex_api).await;
+    let translator = translator(&server);
+
+    let res = translator.translate(
         TEXT.to_string(),
         InputLanguage::Defined(Language::English),
         Language::French,
     );
 
-    res.await.unwrap();
+    assert_eq!(res.await, Ok(format!("[fr] {}", TEXT)));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(query_param(&requests[0], "lang").as_deref(), Some("en-fr"));
 }
 
 #[tokio::test]
 async fn api_single_translate_automatic_language() {
-    let res = TRANSLATOR.translate(TEXT.to_string(), InputLanguage::Automatic, Language::French);
+    let server = MockServer::start(yandex_api).await;
+    let translator = translator(&server);
+
+    let res = translator.translate(TEXT.to_string(), InputLanguage::Automatic, Language::French);
 
-    res.await.unwrap();
+    assert_eq!(res.await, Ok(format!("[fr] {}", TEXT)));
+    assert_eq!(
+        query_param(&server.requests()[0], "lang").as_deref(),
+        Some("fr")
+    );
 }
 
 #[tokio::test]
 async fn api_single_detect_language() {
-    let res = TRANSLATOR.detect(TEXT.to_string()).await;
+    let server = MockServer::start(yandex_api).await;
+    let translator = translator(&server);
+
+    let res = translator.detect(TEXT.to_string()).await;
 
     match res {
         Ok(response) => match response {
@@ -40,18 +125,23 @@ async fn api_single_detect_language() {
 
 #[tokio::test]
 async fn api_translate_and_detect_all_languages() {
+    let server = MockServer::start(yandex_api).await;
+    // share the connections between the many requests
+    let client = HttpClient::new();
+    let translator = translator(&server).with_client(&client);
+
     let fails = Language::iterator()
         .cloned()
         .map(|language| async move {
             let mut hard_failed_tasks = 0u32;
             let mut soft_failed_tasks = 0u32;
 
-            let res = TRANSLATOR
+            let res = translator
                 .translate(TEXT.to_string(), InputLanguage::Automatic, language)
                 .await;
 
             match res {
-                Ok(translation) => match TRANSLATOR.detect(translation).await {
+                Ok(translation) => match translator.detect(translation).await {
                     Ok(val) => match val {
                         Some(lang) => {
                             if lang == language {
@@ -80,7 +170,7 @@ async fn api_translate_and_detect_all_languages() {
                 }
             }
 
-            return (hard_failed_tasks, soft_failed_tasks);
+            (hard_failed_tasks, soft_failed_tasks)
         })
         .collect::<Vec<_>>();
 
@@ -114,6 +204,9 @@ async fn api_translate_and_detect_all_languages() {
 
 #[tokio::test]
 async fn api_translate_long_text() {
+    let server = MockServer::start(yandex_api).await;
+    let translator = translator(&server);
+
     const LONG_TEXT: &str = r#"Bannis ! bannis ! bannis ! c'est là la destinée.
 Ce qu'apporté le flux sera dans la journée
 Repris par le reflux.
@@ -159,7 +252,7 @@ Au fond de nos tombeaux !
 
 Victor Hugo - Les Châtiments "#;
 
-    let res = TRANSLATOR
+    let res = translator
         .translate(
             LONG_TEXT.to_string(),
             InputLanguage::Automatic,
@@ -167,16 +260,15 @@ Victor Hugo - Les Châtiments "#;
         )
         .await;
 
-    match res {
-        Ok(translation) => println!("Translated to English : {}", translation),
-        Err(err) => {
-            println!("Could not translate to English : {:#?}", err);
-        }
-    }
+    // the whole text fits in a single request
+    assert_eq!(res, Ok(format!("[en] {}", LONG_TEXT)));
 }
 
 #[tokio::test]
 async fn api_translate_too_long_text() {
+    let server = MockServer::start(yandex_api).await;
+    let translator = translator(&server);
+
     let mut too_long_text = String::from(
         r#"
 I
@@ -356,7 +448,7 @@ Dieu seul parle aux axes des cieux.
     too_long_text.push_str(&too_long_text.clone());
     too_long_text.push_str(&too_long_text.clone());
 
-    let res = TRANSLATOR
+    let res = translator
         .translate(too_long_text, InputLanguage::Automatic, Language::English)
         .await;
 
@@ -364,7 +456,9 @@ Dieu seul parle aux axes des cieux.
         Ok(_) => {
             panic!("should have been error 413: MaxTextSizeExceeded (translation did not fail)")
         }
-        Err(Error::YandexAPIError(yandex::YandexError::MaxTextSizeExceeded)) => {
+        Err(Error::YandexAPIError(error))
+            if *error.get_kind() == yandex::YandexError::MaxTextSizeExceeded =>
+        {
             println!("API failed on too long text")
         }
         Err(err) => panic!(
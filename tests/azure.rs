@@ -0,0 +1,164 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+#[tokio::test]
+async fn azure_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"[{"translations":[{"text":"Hallo, Welt!","to":"de"}]}]"#,
+        )
+    })
+    .await;
+    let translator = AzureTranslator::with_key("secret")
+        .with_region("westeurope")
+        .with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello, world!".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo, Welt!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/translate?api-version=3.0&from=en&to=de");
+    assert_eq!(
+        requests[0].header("ocp-apim-subscription-key"),
+        Some("secret")
+    );
+    assert_eq!(
+        requests[0].header("ocp-apim-subscription-region"),
+        Some("westeurope")
+    );
+    assert_eq!(requests[0].body, r#"[{"Text":"Hello, world!"}]"#);
+}
+
+#[tokio::test]
+async fn azure_translate_to_many() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"[{"detectedLanguage":{"language":"en","score":1.0},"translations":[{"text":"你好","to":"zh-Hans"},{"text":"Bonjour","to":"fr"}]}]"#,
+        )
+    })
+    .await;
+    let translator = AzureTranslator::with_key("secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate_to_many(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            &[Language::Chinese, Language::French],
+        )
+        .await;
+    assert_eq!(res, Ok(vec!["你好".to_string(), "Bonjour".to_string()]));
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].uri,
+        "/translate?api-version=3.0&to=zh-Hans&to=fr"
+    );
+    assert_eq!(requests[0].header("ocp-apim-subscription-region"), None);
+}
+
+#[tokio::test]
+async fn azure_detect() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"[{"language":"nb","score":0.92,"isTranslationSupported":true,"isTransliterationSupported":false}]"#,
+        )
+    })
+    .await;
+    let translator = AzureTranslator::with_key("secret").with_base_url(&server.url);
+
+    let res = translator.detect("Hei, verden!".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::Norwegian)));
+    assert_eq!(server.requests()[0].uri, "/detect?api-version=3.0");
+}
+
+#[tokio::test]
+async fn azure_errors() {
+    let server = MockServer::start(|req| match req.header("ocp-apim-subscription-key") {
+        Some("exhausted") => json(
+            403,
+            r#"{"error":{"code":403001,"message":"The operation is not allowed because the subscription has exceeded its free quota."}}"#,
+        ),
+        Some("invalid") => json(
+            401,
+            r#"{"error":{"code":401000,"message":"The request is not authorized because credentials are missing or invalid."}}"#,
+        ),
+        _ => json(502, "Bad Gateway"),
+    })
+    .await;
+
+    let res = AzureTranslator::with_key("exhausted")
+        .with_base_url(&server.url)
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::AzureAPIError(
+            ProviderError::new(azure::AzureError::QuotaExceeded, 403)
+                .with_code("403001")
+                .with_message("The operation is not allowed because the subscription has exceeded its free quota.")
+        ))
+    );
+
+    let res = AzureTranslator::with_key("invalid")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::AzureAPIError(
+            ProviderError::new(azure::AzureError::InvalidCredentials, 401)
+                .with_code("401000")
+                .with_message(
+                    "The request is not authorized because credentials are missing or invalid."
+                )
+        ))
+    );
+
+    // without a JSON body, the error comes from the HTTP status
+    let res = AzureTranslator::with_key("other")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::AzureAPIError(
+            azure::AzureError::UnknownErrorCode(502_000).into()
+        ))
+    );
+}
+
+#[test]
+fn azure_error_codes() {
+    use azure::AzureError;
+
+    assert_eq!(
+        AzureError::from_azure_code(400036),
+        AzureError::InvalidTargetLanguage
+    );
+    assert_eq!(
+        AzureError::from_azure_code(400050),
+        AzureError::MaxTextSizeExceeded
+    );
+    assert_eq!(
+        AzureError::from_azure_code(429001),
+        AzureError::TooManyRequests
+    );
+    assert_eq!(AzureError::QuotaExceeded.to_error_code(), 403);
+}
@@ -0,0 +1,113 @@
+mod common;
+
+use common::{form_field, json, MockServer};
+use text_translator::{baidu::BaiduError, *};
+
+#[test]
+fn baidu_sign() {
+    // example of the official documentation
+    assert_eq!(
+        baidu::sign("2015063000000001", "apple", "1435660288", "12345678"),
+        "f89f9594663708c1605f3d736d01d2d4"
+    );
+    // the text is signed before being url-encoded
+    assert_eq!(
+        baidu::sign("2015063000000001", "8个苹果", "1435660288", "秘密"),
+        "62b9a0371f29b9f682a04a0f4f18f5e1"
+    );
+}
+
+#[tokio::test]
+async fn baidu_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"from":"en","to":"zh","trans_result":[{"src":"Hello","dst":"你好"},{"src":"world","dst":"世界"}]}"#,
+        )
+    })
+    .await;
+    let translator = Baidu::with_credentials("app-id", "secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello\nworld".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Chinese,
+        )
+        .await;
+    assert_eq!(res, Ok("你好\n世界".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/translate");
+
+    let body = &requests[0].body;
+    assert_eq!(form_field(body, "q"), Some("Hello%0Aworld"));
+    assert_eq!(form_field(body, "from"), Some("en"));
+    assert_eq!(form_field(body, "to"), Some("zh"));
+    assert_eq!(form_field(body, "appid"), Some("app-id"));
+    let salt = form_field(body, "salt").unwrap();
+    assert_eq!(
+        form_field(body, "sign"),
+        Some(baidu::sign("app-id", "Hello\nworld", salt, "secret").as_str())
+    );
+}
+
+#[tokio::test]
+async fn baidu_errors() {
+    let server = MockServer::start(|req| {
+        if req.body.contains("to=kor") {
+            json(
+                200,
+                r#"{"error_code":"54003","error_msg":"Invalid Access Limit"}"#,
+            )
+        } else {
+            json(200, r#"{"error_code":"54001","error_msg":"Invalid Sign"}"#)
+        }
+    })
+    .await;
+    let translator = Baidu::with_credentials("app-id", "secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::Korean,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::BaiduAPIError(
+            ProviderError::new(BaiduError::AccessFrequencyLimited, 200)
+                .with_code("54003")
+                .with_message("Invalid Access Limit")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::BaiduAPIError(
+            ProviderError::new(BaiduError::InvalidSign, 200)
+                .with_code("54001")
+                .with_message("Invalid Sign")
+        ))
+    );
+
+    let res = Baidu::new()
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+    assert_eq!(server.requests().len(), 2);
+}
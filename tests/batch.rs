@@ -0,0 +1,255 @@
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use text_translator::*;
+
+/// Builds a response with the given status code and body.
+fn response(status: u16, body: &str) -> Result<http::Response<Vec<u8>>, Error> {
+    Ok(http::Response::builder()
+        .status(status)
+        .body(body.as_bytes().to_vec())
+        .unwrap())
+}
+
+/// Answers a Google translation request with the texts in uppercase.
+fn google_v2_translations(request: &MemoryRequest) -> Result<http::Response<Vec<u8>>, Error> {
+    let body: Value = serde_json::from_str(&request.body_text()).unwrap();
+    let translations = body["q"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|q| json!({ "translatedText": q.as_str().unwrap().to_uppercase() }))
+        .collect::<Vec<_>>();
+
+    response(
+        200,
+        &json!({ "data": { "translations": translations } }).to_string(),
+    )
+}
+
+fn texts(count: usize) -> Vec<String> {
+    (0..count).map(|i| format!("text {}", i)).collect()
+}
+
+/// An engine translating the texts in uppercase, failing on empty ones.
+struct Uppercase;
+
+#[async_trait]
+impl Api for Uppercase {
+    fn new() -> Self {
+        Uppercase
+    }
+
+    async fn translate(
+        &self,
+        text: String,
+        _source_language: InputLanguage,
+        _target_language: Language,
+    ) -> Result<String, Error> {
+        match text.as_str() {
+            "" => Err(Error::LingvaAPIError(
+                lingva::LingvaError::BadRequest.into(),
+            )),
+            text => Ok(text.to_uppercase()),
+        }
+    }
+}
+
+#[tokio::test]
+async fn default_batch() {
+    let res = Uppercase
+        .translate_batch(
+            vec!["hello".to_string(), "world".to_string()],
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok(vec!["HELLO".to_string(), "WORLD".to_string()]));
+
+    let res = Uppercase
+        .translate_batch(
+            vec!["hello".to_string(), "".to_string(), "world".to_string()],
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    let err = Error::LingvaAPIError(lingva::LingvaError::BadRequest.into());
+    assert_eq!(
+        res,
+        Err(Error::BatchError(vec![
+            Ok("HELLO".to_string()),
+            Err(err.clone()),
+            Ok("WORLD".to_string()),
+        ]))
+    );
+    let res = res.unwrap_err();
+    assert_eq!(res.to_string(), "1 of 3 texts could not be translated");
+    assert!(!res.is_retryable());
+}
+
+#[tokio::test]
+async fn google_v2_batch() {
+    let transport = MemoryTransport::new(google_v2_translations);
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    let res = translator
+        .translate_batch(
+            texts(130),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Ok(texts(130).iter().map(|text| text.to_uppercase()).collect())
+    );
+
+    // the texts are sent in order, 128 texts at most per request
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    let body: Value = serde_json::from_str(&requests[1].body_text()).unwrap();
+    assert_eq!(
+        body,
+        json!({ "q": ["text 128", "text 129"], "source": "en", "target": "fr", "format": "text" })
+    );
+
+    // no request is sent for an empty batch
+    let res = translator
+        .translate_batch(Vec::new(), InputLanguage::Automatic, Language::French)
+        .await;
+    assert_eq!(res, Ok(Vec::new()));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[tokio::test]
+async fn google_v2_batch_characters() {
+    let transport = MemoryTransport::new(google_v2_translations);
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    let texts = vec!["a".repeat(3000), "b".repeat(3000), "c".repeat(6000)];
+    let res = translator
+        .translate_batch(texts.clone(), InputLanguage::Automatic, Language::French)
+        .await;
+    assert_eq!(
+        res,
+        Ok(texts.iter().map(|text| text.to_uppercase()).collect())
+    );
+
+    // up to 5000 characters per request, a longer text being sent alone
+    assert_eq!(transport.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn google_v2_batch_partial_failure() {
+    let client = HttpClient::with_transport(MemoryTransport::new(|request| {
+        if request.body_text().contains("text 128") {
+            response(
+                503,
+                include_str!("fixtures/errors/google_v2/backend_error.json"),
+            )
+        } else {
+            google_v2_translations(request)
+        }
+    }));
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    let results = match translator
+        .translate_batch(texts(130), InputLanguage::Automatic, Language::French)
+        .await
+    {
+        Err(Error::BatchError(results)) => results,
+        res => panic!("unexpected result: {:?}", res),
+    };
+
+    // the translations of the first request are kept
+    assert_eq!(results.len(), 130);
+    assert_eq!(results[0], Ok("TEXT 0".to_string()));
+    assert_eq!(results[127], Ok("TEXT 127".to_string()));
+    for result in &results[128..] {
+        match result {
+            Err(err @ Error::GoogleV2APIError(_)) => assert!(err.is_retryable()),
+            res => panic!("unexpected result: {:?}", res),
+        }
+    }
+    assert!(Error::BatchError(results).is_retryable());
+}
+
+#[tokio::test]
+async fn batch_before_request_errors() {
+    let transport = MemoryTransport::new(google_v2_translations);
+    let client = HttpClient::with_transport(transport.clone());
+
+    let res = GoogleV2::with_key("key")
+        .with_client(&client)
+        .translate_batch(
+            texts(2),
+            InputLanguage::Defined(Language::French),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::SameLanguages(Language::French, Language::French))
+    );
+
+    let res = GoogleV2::new()
+        .translate_batch(texts(2), InputLanguage::Automatic, Language::French)
+        .await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+
+    assert!(transport.requests().is_empty());
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn yandex_batch() {
+    let transport = MemoryTransport::new(|_| {
+        response(
+            200,
+            r#"{"code":200,"lang":"en-fr","text":["Bonjour","Monde"]}"#,
+        )
+    });
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = Yandex::with_key("key").with_client(&client);
+
+    let res = translator
+        .translate_batch(
+            vec!["Hello".to_string(), "World & co".to_string()],
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok(vec!["Bonjour".to_string(), "Monde".to_string()]));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].uri,
+        "https://translate.yandex.net/api/v1.5/tr.json/translate?key=key&lang=en-fr"
+    );
+    assert_eq!(
+        requests[0].header("Content-Type"),
+        Some("application/x-www-form-urlencoded")
+    );
+    assert_eq!(requests[0].body_text(), "text=Hello&text=World%20%26%20co");
+
+    // a response missing translations fails the texts of its request
+    let res = translator
+        .translate_batch(
+            vec!["Hello".to_string(), "World".to_string(), "!".to_string()],
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::BatchError(vec![
+            Err(Error::CouldNotDerializeJson),
+            Err(Error::CouldNotDerializeJson),
+            Err(Error::CouldNotDerializeJson),
+        ]))
+    );
+}
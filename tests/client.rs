@@ -0,0 +1,101 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+const TRANSLATE_RESPONSE: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+#[tokio::test]
+async fn client_reuses_connections() {
+    let server = MockServer::start(|_| json(200, TRANSLATE_RESPONSE)).await;
+    let client = HttpClient::new();
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    for _ in 0..3 {
+        let res = translator
+            .translate(
+                "Hello".to_string(),
+                InputLanguage::Automatic,
+                Language::French,
+            )
+            .await;
+        assert_eq!(res, Ok("Bonjour".to_string()));
+    }
+
+    assert_eq!(server.requests().len(), 3);
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn client_shared_between_engines() {
+    let server = MockServer::start(|req| {
+        if req.uri.starts_with("/api/v1/") {
+            json(200, TRANSLATE_RESPONSE)
+        } else {
+            json(200, r#"{"translatedText":"Bonjour"}"#)
+        }
+    })
+    .await;
+    let client = HttpClient::new();
+    let lingva = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+    let libretranslate = LibreTranslate::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    let res = lingva
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("Bonjour".to_string()));
+    let res = libretranslate
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("Bonjour".to_string()));
+
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn without_client_opens_a_connection_per_request() {
+    let server = MockServer::start(|_| json(200, TRANSLATE_RESPONSE)).await;
+    let translator = Lingva::new().with_base_url(&server.url);
+
+    for _ in 0..3 {
+        translator
+            .translate(
+                "Hello".to_string(),
+                InputLanguage::Automatic,
+                Language::French,
+            )
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(server.connections(), 3);
+}
+
+#[test]
+fn client_identity() {
+    let client = HttpClient::new();
+    let other = HttpClient::new();
+
+    // clones share the same pool
+    assert_eq!(client, client.clone());
+    assert_ne!(client, other);
+
+    let translator = Lingva::new().with_client(&client);
+    assert_eq!(translator, Lingva::new().with_client(&client.clone()));
+    assert_ne!(translator, Lingva::new().with_client(&other));
+    assert_ne!(translator, Lingva::new());
+}
@@ -0,0 +1,209 @@
+//! Helpers shared by the integration tests.
+//!
+//! [`MockServer`](struct.MockServer.html) is a small HTTP server bound to a random local port,
+//! used as a stand-in for the translation APIs so that the tests can run offline. It can also serve
+//! HTTPS, with a self-signed certificate for `localhost`.
+
+#![allow(dead_code)]
+
+use std::convert::Infallible;
+use std::net::TcpListener;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+
+use hyper::{
+    body::to_bytes,
+    header::HeaderMap,
+    server::conn::Http,
+    service::{make_service_fn, service_fn},
+    Body, Request, Response, Server,
+};
+use tokio_rustls::{
+    rustls::{Certificate, NoClientAuth, PrivateKey, ServerConfig},
+    TlsAcceptor,
+};
+
+/// A request received by the mock server.
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: String,
+    /// Path and query of the request.
+    pub uri: String,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl Recorded {
+    /// Returns the value of the given header, if it was set.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+}
+
+/// A local HTTP server answering every request with the given handler.
+pub struct MockServer {
+    pub url: String,
+    /// PEM certificate of the server, when serving HTTPS.
+    pub certificate: Option<String>,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl MockServer {
+    /// Starts the server on a random port, in the current tokio runtime.
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Recorded) -> Response<Body> + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let recorded = requests.clone();
+        let accepted = connections.clone();
+        let make_service = make_service_fn(move |_| {
+            accepted.fetch_add(1, Ordering::SeqCst);
+            let handler = handler.clone();
+            let recorded = recorded.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                    let handler = handler.clone();
+                    let recorded = recorded.clone();
+                    async move {
+                        let (parts, body) = req.into_parts();
+                        let body = to_bytes(body).await.unwrap_or_default();
+                        let request = Recorded {
+                            method: parts.method.to_string(),
+                            uri: parts.uri.to_string(),
+                            headers: parts.headers,
+                            body: String::from_utf8_lossy(&body).into_owned(),
+                        };
+                        let response = handler(&request);
+                        recorded.lock().unwrap().push(request);
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+
+        let server = Server::from_tcp(listener)
+            .expect("start mock server")
+            .serve(make_service);
+        tokio::spawn(async move {
+            let _ = server.await;
+        });
+
+        Self {
+            url,
+            certificate: None,
+            requests,
+            connections,
+        }
+    }
+
+    /// Starts the server on a random port over HTTPS, in the current tokio runtime.
+    ///
+    /// The server is reached at `https://localhost`, with a self-signed certificate.
+    pub async fn start_tls<F>(handler: F) -> Self
+    where
+        F: Fn(&Recorded) -> Response<Body> + Send + Sync + 'static,
+    {
+        let handler = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let generated = rcgen::generate_simple_self_signed(vec!["localhost".to_string()])
+            .expect("generate certificate");
+        let certificate = generated.cert.pem();
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        config
+            .set_single_cert(
+                vec![Certificate(generated.cert.der().to_vec())],
+                PrivateKey(generated.key_pair.serialize_der()),
+            )
+            .expect("load certificate");
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!(
+            "https://localhost:{}",
+            listener.local_addr().unwrap().port()
+        );
+        let mut listener = tokio::net::TcpListener::from_std(listener).expect("bind mock server");
+
+        let recorded = requests.clone();
+        let accepted = connections.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                accepted.fetch_add(1, Ordering::SeqCst);
+                let acceptor = acceptor.clone();
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let stream = match acceptor.accept(stream).await {
+                        Ok(stream) => stream,
+                        Err(_) => return,
+                    };
+                    let service = service_fn(move |req: Request<Body>| {
+                        let handler = handler.clone();
+                        let recorded = recorded.clone();
+                        async move {
+                            let (parts, body) = req.into_parts();
+                            let body = to_bytes(body).await.unwrap_or_default();
+                            let request = Recorded {
+                                method: parts.method.to_string(),
+                                uri: parts.uri.to_string(),
+                                headers: parts.headers,
+                                body: String::from_utf8_lossy(&body).into_owned(),
+                            };
+                            let response = handler(&request);
+                            recorded.lock().unwrap().push(request);
+                            Ok::<_, Infallible>(response)
+                        }
+                    });
+                    let _ = Http::new().serve_connection(stream, service).await;
+                });
+            }
+        });
+
+        Self {
+            url,
+            certificate: Some(certificate),
+            requests,
+            connections,
+        }
+    }
+
+    /// Returns every request received so far.
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Returns the number of connections accepted so far.
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+/// Builds a JSON response with the given status code.
+pub fn json(status: u16, body: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// Returns the value of a field of an url-encoded form.
+pub fn form_field<'a>(body: &'a str, name: &str) -> Option<&'a str> {
+    body.split('&')
+        .filter_map(|field| field.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
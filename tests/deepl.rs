@@ -0,0 +1,135 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+const TRANSLATE_RESPONSE: &str =
+    r#"{"translations":[{"detected_source_language":"EN","text":"Hallo, Welt!"}]}"#;
+
+#[tokio::test]
+async fn deepl_translate() {
+    let server = MockServer::start(|_| json(200, TRANSLATE_RESPONSE)).await;
+    let base_url = format!("{}/v2", server.url);
+    let translator = DeepL::with_key("secret:fx").with_base_url(&base_url);
+
+    let res = translator
+        .translate(
+            "Hello, world!".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo, Welt!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/v2/translate");
+    assert_eq!(
+        requests[0].header("authorization"),
+        Some("DeepL-Auth-Key secret:fx")
+    );
+    assert_eq!(
+        requests[0].body,
+        "text=Hello%2C%20world%21&target_lang=DE&source_lang=EN"
+    );
+}
+
+#[tokio::test]
+async fn deepl_translate_regional_target() {
+    let server = MockServer::start(|_| json(200, TRANSLATE_RESPONSE)).await;
+    let translator = DeepL::with_key("secret").with_base_url(&server.url);
+
+    translator
+        .translate(
+            "Hallo".to_string(),
+            InputLanguage::Automatic,
+            Language::English,
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(server.requests()[0].body, "text=Hallo&target_lang=EN-US");
+}
+
+#[tokio::test]
+async fn deepl_detect() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"translations":[{"detected_source_language":"FR","text":"Hello"}]}"#,
+        )
+    })
+    .await;
+    let translator = DeepL::with_key("secret").with_base_url(&server.url);
+
+    let res = translator.detect("Bonjour".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::French)));
+}
+
+#[tokio::test]
+async fn deepl_errors() {
+    let server = MockServer::start(|req| match req.header("authorization") {
+        Some("DeepL-Auth-Key exhausted") => json(456, r#"{"message":"Quota exceeded"}"#),
+        _ => json(403, r#"{"message":"Wrong endpoint"}"#),
+    })
+    .await;
+
+    let res = DeepL::with_key("exhausted")
+        .with_base_url(&server.url)
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::DeepLAPIError(
+            ProviderError::new(deepl::DeepLError::QuotaExceeded, 456)
+                .with_message("Quota exceeded")
+        ))
+    );
+
+    let res = DeepL::with_key("invalid")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::DeepLAPIError(
+            ProviderError::new(deepl::DeepLError::AuthorizationFailed, 403)
+                .with_message("Wrong endpoint")
+        ))
+    );
+}
+
+#[tokio::test]
+async fn deepl_no_key() {
+    let res = DeepL::new()
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+}
+
+#[test]
+fn deepl_endpoint_from_key() {
+    assert_eq!(
+        DeepL::with_key("0000-0000:fx").base_url(),
+        deepl::DEEPL_FREE_BASE_URL
+    );
+    assert_eq!(
+        DeepL::with_key("0000-0000").base_url(),
+        deepl::DEEPL_PRO_BASE_URL
+    );
+    assert_eq!(
+        DeepL::with_key("0000-0000:fx")
+            .with_base_url("http://localhost/v2")
+            .base_url(),
+        "http://localhost/v2"
+    );
+}
@@ -0,0 +1,729 @@
+// the fixtures are error responses documented by each provider
+use text_translator::*;
+
+/// Asserts that each error response, with its HTTP status code, is parsed into the expected error.
+fn check<E: ApiError + std::fmt::Debug + PartialEq>(cases: &[(u16, &str, ProviderError<E>)]) {
+    for (status, body, expected) in cases {
+        assert_eq!(&E::from_response(*status, body), expected, "{}", body);
+    }
+}
+
+#[allow(deprecated)]
+#[test]
+fn yandex_errors() {
+    use yandex::YandexError::*;
+
+    check(&[
+        (
+            401,
+            include_str!("fixtures/errors/yandex/invalid_api_key.json"),
+            ProviderError::new(InvalidAPIKey, 401)
+                .with_code("401")
+                .with_message("API key is invalid"),
+        ),
+        (
+            402,
+            include_str!("fixtures/errors/yandex/blocked_api_key.json"),
+            ProviderError::new(BlockedAPIKey, 402)
+                .with_code("402")
+                .with_message("API key is blocked"),
+        ),
+        (
+            404,
+            include_str!("fixtures/errors/yandex/daily_limit_exceeded.json"),
+            ProviderError::new(DailyLimitExceeded, 404)
+                .with_code("404")
+                .with_message("Exceeded the daily limit on the amount of translated text"),
+        ),
+        (
+            413,
+            include_str!("fixtures/errors/yandex/max_text_size_exceeded.json"),
+            ProviderError::new(MaxTextSizeExceeded, 413)
+                .with_code("413")
+                .with_message("Text size exceeds the maximum"),
+        ),
+        (
+            422,
+            include_str!("fixtures/errors/yandex/could_not_translate.json"),
+            ProviderError::new(CouldNotTranslate, 422)
+                .with_code("422")
+                .with_message("The text cannot be translated"),
+        ),
+        (
+            501,
+            include_str!("fixtures/errors/yandex/direction_not_supported.json"),
+            ProviderError::new(TranslationDirectionNotSupported, 501)
+                .with_code("501")
+                .with_message("The specified translation direction is not supported"),
+        ),
+        // without body, the error comes from the HTTP status
+        (401, "", ProviderError::new(InvalidAPIKey, 401)),
+    ]);
+}
+
+#[test]
+fn google_v2_errors() {
+    use google_v2::GoogleV2Error::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/google_v2/key_invalid.json"),
+            ProviderError::new(InvalidAPIKey, 400)
+                .with_code("API_KEY_INVALID")
+                .with_message("API key not valid. Please pass a valid API key."),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/google_v2/key_invalid_legacy.json"),
+            ProviderError::new(InvalidAPIKey, 400)
+                .with_code("keyInvalid")
+                .with_message("Bad Request"),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/google_v2/access_not_configured.json"),
+            ProviderError::new(BlockedAPIKey, 403)
+                .with_code("accessNotConfigured")
+                .with_message("Cloud Translation API has not been used in project 123456 before or it is disabled."),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/google_v2/daily_limit_exceeded.json"),
+            ProviderError::new(DailyLimitExceeded, 403)
+                .with_code("dailyLimitExceeded")
+                .with_message("Daily Limit Exceeded"),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/google_v2/user_rate_limit_exceeded.json"),
+            ProviderError::new(RateLimitExceeded, 403)
+                .with_code("userRateLimitExceeded")
+                .with_message("User Rate Limit Exceeded"),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/google_v2/quota_exceeded.json"),
+            ProviderError::new(RateLimitExceeded, 429)
+                .with_code("rateLimitExceeded")
+                .with_message("Quota exceeded for quota metric 'v2 and v3 general model characters' and limit 'v2 and v3 general model characters per minute per user'."),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/google_v2/bad_language_pair.json"),
+            ProviderError::new(TranslationDirectionNotSupported, 400)
+                .with_code("invalid")
+                .with_message("Bad language pair: en|mrj"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/google_v2/text_too_long.json"),
+            ProviderError::new(MaxTextSizeExceeded, 400)
+                .with_code("invalid")
+                .with_message("Text too long"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/google_v2/invalid_value.json"),
+            ProviderError::new(InvalidRequest, 400)
+                .with_code("invalid")
+                .with_message("Invalid Value"),
+        ),
+        (
+            500,
+            include_str!("fixtures/errors/google_v2/backend_error.json"),
+            ProviderError::new(CouldNotTranslate, 500)
+                .with_code("backendError")
+                .with_message("Backend Error"),
+        ),
+        // the status code is no longer shifted
+        (401, "Unauthorized", ProviderError::new(InvalidAPIKey, 401)),
+        (502, "", ProviderError::new(UnknownErrorCode(502), 502)),
+    ]);
+}
+
+#[test]
+fn deepl_errors() {
+    use deepl::DeepLError::*;
+
+    check(&[
+        (
+            403,
+            include_str!("fixtures/errors/deepl/authorization_failed.json"),
+            ProviderError::new(AuthorizationFailed, 403)
+                .with_message("Wrong endpoint. Use https://api.deepl.com"),
+        ),
+        (
+            456,
+            include_str!("fixtures/errors/deepl/quota_exceeded.json"),
+            ProviderError::new(QuotaExceeded, 456).with_message("Quota Exceeded"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/deepl/bad_request.json"),
+            ProviderError::new(BadRequest, 400).with_message(
+                "Value for 'target_lang' not supported. Supported target languages can be found with the /languages endpoint.",
+            ),
+        ),
+        (529, "", ProviderError::new(TooManyRequests, 529)),
+    ]);
+}
+
+#[test]
+fn azure_errors() {
+    use azure::AzureError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/azure/invalid_target_language.json"),
+            ProviderError::new(InvalidTargetLanguage, 400)
+                .with_code("400036")
+                .with_message("The target language is not valid."),
+        ),
+        (
+            401,
+            include_str!("fixtures/errors/azure/invalid_credentials.json"),
+            ProviderError::new(InvalidCredentials, 401)
+                .with_code("401000")
+                .with_message("The request is not authorized because credentials are missing or invalid."),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/azure/quota_exceeded.json"),
+            ProviderError::new(QuotaExceeded, 403)
+                .with_code("403001")
+                .with_message("The operation is not allowed because the subscription has exceeded its free quota."),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/azure/too_many_requests.json"),
+            ProviderError::new(TooManyRequests, 429)
+                .with_code("429001")
+                .with_message("The server rejected the request because the client has exceeded request limits."),
+        ),
+    ]);
+}
+
+#[test]
+fn amazon_errors() {
+    use amazon::AmazonError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/amazon/unsupported_language_pair.json"),
+            ProviderError::new(UnsupportedLanguagePair, 400)
+                .with_code("UnsupportedLanguagePairException")
+                .with_message("Unsupported language pair: en to mrj"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/amazon/text_size_limit_exceeded.json"),
+            ProviderError::new(TextSizeLimitExceeded, 400)
+                .with_code("TextSizeLimitExceededException")
+                .with_message("Input text size exceeds limit. Max length of request text allowed is 10000 bytes while in this request the text size is 10011 bytes"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/amazon/throttling.json"),
+            ProviderError::new(TooManyRequests, 400)
+                .with_code("ThrottlingException")
+                .with_message("Rate exceeded"),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/amazon/unrecognized_client.json"),
+            ProviderError::new(UnrecognizedClient, 403)
+                .with_code("UnrecognizedClientException")
+                .with_message("The security token included in the request is invalid."),
+        ),
+    ]);
+}
+
+#[test]
+fn google_v3_errors() {
+    use google_v3::GoogleV3Error::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/google_v3/invalid_argument.json"),
+            ProviderError::new(InvalidArgument, 400)
+                .with_code("INVALID_ARGUMENT")
+                .with_message("Target language is invalid."),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/google_v3/permission_denied.json"),
+            ProviderError::new(PermissionDenied, 403)
+                .with_code("PERMISSION_DENIED")
+                .with_message("Cloud Translation API has not been used in project 123456 before or it is disabled."),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/google_v3/resource_exhausted.json"),
+            ProviderError::new(ResourceExhausted, 429)
+                .with_code("RESOURCE_EXHAUSTED")
+                .with_message("Quota exceeded for quota metric 'Number of requests'."),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/google_v3/invalid_grant.json"),
+            ProviderError::new(InvalidGrant, 400)
+                .with_code("invalid_grant")
+                .with_message("Invalid JWT Signature."),
+        ),
+    ]);
+}
+
+#[test]
+fn yandex_cloud_errors() {
+    use yandex_cloud::YandexCloudError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/yandex_cloud/invalid_argument.json"),
+            ProviderError::new(InvalidArgument, 400)
+                .with_code("3")
+                .with_message("unsupported target_language_code: mrj"),
+        ),
+        (
+            401,
+            include_str!("fixtures/errors/yandex_cloud/unauthenticated.json"),
+            ProviderError::new(Unauthenticated, 401)
+                .with_code("16")
+                .with_message("Unknown api key"),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/yandex_cloud/resource_exhausted.json"),
+            ProviderError::new(ResourceExhausted, 429)
+                .with_code("8")
+                .with_message("limit on units was exceeded. Limit: 1000000, Interval: 1h0m0s"),
+        ),
+    ]);
+}
+
+#[test]
+fn papago_errors() {
+    use papago::PapagoError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/papago/unsupported_source_language.json"),
+            ProviderError::new(UnsupportedSourceLanguage, 400)
+                .with_code("N2MT02")
+                .with_message("Unsupported source language"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/papago/text_too_long.json"),
+            ProviderError::new(TextTooLong, 400)
+                .with_code("N2MT08")
+                .with_message("Text too long"),
+        ),
+        (
+            401,
+            include_str!("fixtures/errors/papago/authentication_failed.json"),
+            ProviderError::new(AuthenticationFailed, 401)
+                .with_code("200")
+                .with_message("Authentication Failed"),
+        ),
+    ]);
+}
+
+#[test]
+fn baidu_errors() {
+    use baidu::BaiduError::*;
+
+    // the errors are returned with a successful HTTP status
+    check(&[
+        (
+            200,
+            include_str!("fixtures/errors/baidu/unauthorized_user.json"),
+            ProviderError::new(UnauthorizedUser, 200)
+                .with_code("52003")
+                .with_message("UNAUTHORIZED USER"),
+        ),
+        (
+            200,
+            include_str!("fixtures/errors/baidu/access_frequency_limited.json"),
+            ProviderError::new(AccessFrequencyLimited, 200)
+                .with_code("54003")
+                .with_message("Invalid Access Limit"),
+        ),
+        (
+            200,
+            include_str!("fixtures/errors/baidu/unsupported_target_language.json"),
+            ProviderError::new(UnsupportedTargetLanguage, 200)
+                .with_code("58001")
+                .with_message("The target language is not supported"),
+        ),
+        (
+            200,
+            r#"{"error_code":"12345","error_msg":"New error"}"#,
+            ProviderError::new(UnknownBaiduCode, 200)
+                .with_code("12345")
+                .with_message("New error"),
+        ),
+    ]);
+}
+
+#[test]
+fn youdao_errors() {
+    use youdao::YoudaoError::*;
+
+    // the errors are returned with a successful HTTP status, and without message
+    check(&[
+        (
+            200,
+            include_str!("fixtures/errors/youdao/invalid_app_key.json"),
+            ProviderError::new(InvalidAppKey, 200).with_code("108"),
+        ),
+        (
+            200,
+            include_str!("fixtures/errors/youdao/access_frequency_limited.json"),
+            ProviderError::new(AccessFrequencyLimited, 200).with_code("411"),
+        ),
+    ]);
+}
+
+#[test]
+fn mymemory_errors() {
+    use mymemory::MyMemoryError::*;
+
+    check(&[
+        (
+            200,
+            include_str!("fixtures/errors/mymemory/invalid_language_pair.json"),
+            ProviderError::new(InvalidParameter, 200)
+                .with_code("403")
+                .with_message("INVALID LANGUAGE PAIR SPECIFIED. EXAMPLE: LANGPAIR=EN|IT USING 2 LETTER ISO OR RFC3066 LIKE ZH-CN. ALMOST ALL LANGUAGES SUPPORTED BUT SOME MAY HAVE NO CONTENT"),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/mymemory/quota_exceeded.json"),
+            ProviderError::new(QuotaExceeded, 429)
+                .with_code("429")
+                .with_message("MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY. NEXT AVAILABLE IN  10 HOURS 20 MINUTES 30 SECONDS VISIT HTTPS://MYMEMORY.TRANSLATED.NET/DOC/USAGELIMITS.PHP TO TRANSLATE MORE"),
+        ),
+    ]);
+}
+
+#[test]
+fn modernmt_errors() {
+    use modernmt::ModernMtError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/modernmt/unsupported_language.json"),
+            ProviderError::new(UnsupportedLanguage, 400)
+                .with_code("UnsupportedLanguageException")
+                .with_message("Language pair not supported: en > mrj"),
+        ),
+        (
+            401,
+            include_str!("fixtures/errors/modernmt/authentication.json"),
+            ProviderError::new(InvalidApiKey, 401)
+                .with_code("AuthenticationException")
+                .with_message("Invalid authentication token"),
+        ),
+    ]);
+}
+
+#[test]
+fn libretranslate_errors() {
+    use libretranslate::LibreTranslateError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/libretranslate/bad_request.json"),
+            ProviderError::new(BadRequest, 400).with_message("mrj is not supported"),
+        ),
+        (
+            403,
+            include_str!("fixtures/errors/libretranslate/forbidden.json"),
+            ProviderError::new(Forbidden, 403).with_message("Invalid API key"),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/libretranslate/slow_down.json"),
+            ProviderError::new(SlowDown, 429).with_message("Too many request limits violations"),
+        ),
+    ]);
+}
+
+#[test]
+fn lingva_errors() {
+    use lingva::LingvaError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/lingva/bad_request.json"),
+            ProviderError::new(BadRequest, 400).with_message("Invalid target language"),
+        ),
+        (
+            500,
+            include_str!("fixtures/errors/lingva/translation_failed.json"),
+            ProviderError::new(TranslationFailed, 500)
+                .with_message("An error occurred while retrieving the translation"),
+        ),
+    ]);
+}
+
+#[test]
+fn apertium_errors() {
+    use apertium::ApertiumError::*;
+
+    check(&[
+        (
+            400,
+            include_str!("fixtures/errors/apertium/pair_not_installed.json"),
+            ProviderError::new(PairNotInstalled, 400).with_message("That pair is not installed"),
+        ),
+        (
+            400,
+            include_str!("fixtures/errors/apertium/bad_request.json"),
+            ProviderError::new(BadRequest, 400).with_message("Missing argument q"),
+        ),
+    ]);
+}
+
+#[test]
+fn llm_errors() {
+    use llm::LlmError::*;
+
+    check(&[
+        (
+            401,
+            include_str!("fixtures/errors/llm/invalid_api_key.json"),
+            ProviderError::new(InvalidApiKey, 401)
+                .with_code("invalid_api_key")
+                .with_message("Incorrect API key provided: sk-****. You can find your API key at https://platform.openai.com/account/api-keys."),
+        ),
+        (
+            429,
+            include_str!("fixtures/errors/llm/insufficient_quota.json"),
+            ProviderError::new(QuotaExceeded, 429)
+                .with_code("insufficient_quota")
+                .with_message("You exceeded your current quota, please check your plan and billing details."),
+        ),
+        (
+            404,
+            include_str!("fixtures/errors/llm/model_not_found.json"),
+            ProviderError::new(ModelNotFound, 404)
+                .with_code("model_not_found")
+                .with_message("The model `gpt-5` does not exist or you do not have access to it."),
+        ),
+        (
+            500,
+            include_str!("fixtures/errors/llm/server_error.json"),
+            ProviderError::new(ServerError, 500)
+                .with_code("server_error")
+                .with_message("The server had an error while processing your request. Sorry about that!"),
+        ),
+        (
+            404,
+            include_str!("fixtures/errors/llm/plain_message.json"),
+            ProviderError::new(ModelNotFound, 404)
+                .with_message("model 'llama3' not found, try pulling it first"),
+        ),
+    ]);
+}
+
+#[test]
+fn provider_error() {
+    let error = ProviderError::new(deepl::DeepLError::QuotaExceeded, 456)
+        .with_code("quota")
+        .with_message("Quota Exceeded");
+    assert_eq!(error.get_kind(), &deepl::DeepLError::QuotaExceeded);
+    assert_eq!(error.get_status(), 456);
+    assert_eq!(error.get_code(), Some("quota"));
+    assert_eq!(error.get_message(), Some("Quota Exceeded"));
+    assert_eq!(
+        error.to_string(),
+        "DeepL error 456 : QuotaExceeded (quota): Quota Exceeded"
+    );
+
+    // errors detected before sending a request have the status code matching their variant
+    let error = ProviderError::from(apertium::ApertiumError::SourceLanguageRequired);
+    assert_eq!(error.get_status(), 400);
+    assert_eq!(error.get_code(), None);
+    assert_eq!(error.get_message(), None);
+    assert_eq!(
+        error.to_string(),
+        "Apertium error 400 : SourceLanguageRequired"
+    );
+}
+
+/// Returns whether an error is retryable, an authentication failure, a reached limit and an unsupported pair.
+fn classify(error: &Error) -> (bool, bool, bool, bool) {
+    (
+        error.is_retryable(),
+        error.is_auth(),
+        error.is_quota(),
+        error.is_unsupported_pair(),
+    )
+}
+
+#[allow(deprecated)]
+#[test]
+fn classification() {
+    const RETRYABLE: (bool, bool, bool, bool) = (true, false, false, false);
+    const AUTH: (bool, bool, bool, bool) = (false, true, false, false);
+    const RATE_LIMIT: (bool, bool, bool, bool) = (true, false, true, false);
+    const QUOTA: (bool, bool, bool, bool) = (false, false, true, false);
+    const UNSUPPORTED_PAIR: (bool, bool, bool, bool) = (false, false, false, true);
+    const PERMANENT: (bool, bool, bool, bool) = (false, false, false, false);
+
+    #[allow(unused_mut)]
+    let mut cases = vec![
+        (Error::Timeout, RETRYABLE),
+        (
+            Error::TransportError(ErrorSource::new(std::io::Error::new(
+                std::io::ErrorKind::ConnectionReset,
+                "connection reset",
+            ))),
+            RETRYABLE,
+        ),
+        (Error::NoApiKeySet, AUTH),
+        (Error::InvalidCredentials("no such file".to_string()), AUTH),
+        (Error::CouldNotParseUri("http://".to_string()), PERMANENT),
+        (
+            Error::RequestError("invalid header value".to_string()),
+            PERMANENT,
+        ),
+        (
+            Error::SameLanguages(Language::French, Language::French),
+            PERMANENT,
+        ),
+        (
+            Error::YandexAPIError(yandex::YandexError::from_response(
+                401,
+                include_str!("fixtures/errors/yandex/invalid_api_key.json"),
+            )),
+            AUTH,
+        ),
+        (
+            Error::YandexAPIError(yandex::YandexError::DailyLimitExceeded.into()),
+            QUOTA,
+        ),
+        (
+            Error::YandexAPIError(yandex::YandexError::TranslationDirectionNotSupported.into()),
+            UNSUPPORTED_PAIR,
+        ),
+        (
+            Error::YandexCloudAPIError(yandex_cloud::YandexCloudError::ResourceExhausted.into()),
+            RATE_LIMIT,
+        ),
+        (
+            Error::GoogleV2APIError(google_v2::GoogleV2Error::RateLimitExceeded.into()),
+            RATE_LIMIT,
+        ),
+        (
+            Error::GoogleV2APIError(google_v2::GoogleV2Error::DailyLimitExceeded.into()),
+            QUOTA,
+        ),
+        (
+            Error::GoogleV3APIError(google_v3::GoogleV3Error::InvalidGrant.into()),
+            AUTH,
+        ),
+        (
+            Error::DeepLAPIError(deepl::DeepLError::QuotaExceeded.into()),
+            QUOTA,
+        ),
+        (
+            Error::AzureAPIError(azure::AzureError::InvalidTargetLanguage.into()),
+            UNSUPPORTED_PAIR,
+        ),
+        (
+            Error::AzureAPIError(azure::AzureError::UnknownErrorCode(503_001).into()),
+            RETRYABLE,
+        ),
+        (
+            Error::LibreTranslateAPIError(libretranslate::LibreTranslateError::SlowDown.into()),
+            RATE_LIMIT,
+        ),
+        (
+            Error::AmazonAPIError(amazon::AmazonError::ExpiredToken.into()),
+            AUTH,
+        ),
+        (
+            Error::AmazonAPIError(amazon::AmazonError::UnsupportedLanguagePair.into()),
+            UNSUPPORTED_PAIR,
+        ),
+        (
+            Error::MyMemoryAPIError(mymemory::MyMemoryError::QuotaExceeded.into()),
+            QUOTA,
+        ),
+        (
+            Error::LingvaAPIError(lingva::LingvaError::BadRequest.into()),
+            PERMANENT,
+        ),
+        (
+            Error::ApertiumAPIError(apertium::ApertiumError::PairNotInstalled.into()),
+            UNSUPPORTED_PAIR,
+        ),
+        (
+            Error::PapagoAPIError(papago::PapagoError::UnsupportedSourceLanguage.into()),
+            UNSUPPORTED_PAIR,
+        ),
+        (
+            Error::BaiduAPIError(baidu::BaiduError::AccessFrequencyLimited.into()),
+            RATE_LIMIT,
+        ),
+        (
+            Error::BaiduAPIError(baidu::BaiduError::UnknownBaiduCode.into()),
+            PERMANENT,
+        ),
+        (
+            Error::YoudaoAPIError(youdao::YoudaoError::InvalidSignature.into()),
+            AUTH,
+        ),
+        (
+            Error::ModernMtAPIError(modernmt::ModernMtError::PaymentRequired.into()),
+            QUOTA,
+        ),
+        (
+            Error::LlmAPIError(llm::LlmError::ServiceUnavailable.into()),
+            RETRYABLE,
+        ),
+        // the error codes unknown to an engine are classified from their HTTP status code
+        (
+            Error::LlmAPIError(llm::LlmError::UnknownErrorCode(429).into()),
+            RATE_LIMIT,
+        ),
+        (
+            Error::DeepLAPIError(deepl::DeepLError::UnknownErrorCode(403).into()),
+            AUTH,
+        ),
+        (
+            Error::ModernMtAPIError(modernmt::ModernMtError::UnknownErrorCode(418).into()),
+            PERMANENT,
+        ),
+    ];
+
+    #[cfg(feature = "keyless")]
+    cases.push((
+        Error::GoogleWebAPIError(google_web::GoogleWebError::TooManyRequests.into()),
+        RATE_LIMIT,
+    ));
+    #[cfg(feature = "offline")]
+    cases.push((
+        Error::OfflineError(offline::OfflineError::UnsupportedPair(
+            Language::French,
+            Language::Japanese,
+        )),
+        UNSUPPORTED_PAIR,
+    ));
+
+    for (error, expected) in cases {
+        assert_eq!(classify(&error), expected, "{:?}", error);
+    }
+}
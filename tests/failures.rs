@@ -0,0 +1,153 @@
+// every failure of a request is returned as an `Error`, never as a panic
+use std::error::Error as _;
+
+use text_translator::*;
+
+/// Builds a response with the given status code and body.
+fn response(status: u16, body: &[u8]) -> Result<http::Response<Vec<u8>>, Error> {
+    Ok(http::Response::builder()
+        .status(status)
+        .body(body.to_vec())
+        .unwrap())
+}
+
+/// Returns a client answering every request with the given status code and body.
+fn client(status: u16, body: &'static [u8]) -> HttpClient {
+    HttpClient::with_transport(MemoryTransport::new(move |_| response(status, body)))
+}
+
+async fn translate<T: Api>(translator: &T) -> Result<String, Error> {
+    translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn transport_failure() {
+    let client = HttpClient::with_transport(MemoryTransport::new(|_| {
+        Err(Error::TransportError(ErrorSource::new(
+            std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "connection refused"),
+        )))
+    }));
+    let translator = Yandex::with_key("key").with_client(&client);
+
+    for res in [
+        translate(&translator).await.map(|_| ()),
+        translator.detect("Hallo".to_string()).await.map(|_| ()),
+    ] {
+        let err = match res {
+            Err(err @ Error::TransportError(_)) => err,
+            res => panic!("unexpected result: {:?}", res),
+        };
+        let source = err.source().unwrap();
+        let io = source.downcast_ref::<std::io::Error>().unwrap();
+        assert_eq!(io.kind(), std::io::ErrorKind::ConnectionRefused);
+        assert_eq!(
+            err.to_string(),
+            "could not send the request or read its response"
+        );
+    }
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn invalid_response() {
+    let body = b"<html><body>502 Bad Gateway</body></html>";
+
+    let client = client(200, body);
+    let results = [
+        translate(&Yandex::with_key("key").with_client(&client)).await,
+        translate(&GoogleV2::with_key("key").with_client(&client)).await,
+        translate(&DeepL::with_key("key").with_client(&client)).await,
+    ];
+
+    for res in results {
+        let err = match res {
+            Err(err @ Error::InvalidResponse(_)) => err,
+            res => panic!("unexpected result: {:?}", res),
+        };
+        assert!(err
+            .source()
+            .unwrap()
+            .downcast_ref::<serde_json::Error>()
+            .is_some());
+    }
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn invalid_utf8() {
+    let client = client(200, b"{\"code\":200,\"text\":[\"\xff\xfe\"]}");
+    let translator = Yandex::with_key("key").with_client(&client);
+
+    let err = match translate(&translator).await {
+        Err(err @ Error::CouldNotConvertToUtf8Str(_)) => err,
+        res => panic!("unexpected result: {:?}", res),
+    };
+    assert!(err
+        .source()
+        .unwrap()
+        .downcast_ref::<std::str::Utf8Error>()
+        .is_some());
+}
+
+#[tokio::test]
+async fn empty_detection() {
+    let client = client(200, br#"{"data":{"detections":[[]]}}"#);
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    assert_eq!(translator.detect("Hallo".to_string()).await, Ok(None));
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn provider_failure() {
+    let client = client(
+        401,
+        include_bytes!("fixtures/errors/yandex/invalid_api_key.json"),
+    );
+    let translator = Yandex::with_key("key").with_client(&client);
+
+    let err = translate(&translator).await.unwrap_err();
+    assert_eq!(err.to_string(), "Yandex API error");
+
+    let source = err
+        .source()
+        .unwrap()
+        .downcast_ref::<ProviderError<yandex::YandexError>>()
+        .unwrap();
+    assert_eq!(source.get_kind(), &yandex::YandexError::InvalidAPIKey);
+    assert_eq!(source.get_message(), Some("API key is invalid"));
+    assert_eq!(
+        source.to_string(),
+        "Yandex error 401 : InvalidAPIKey: API key is invalid"
+    );
+}
+
+#[test]
+#[allow(deprecated)]
+fn display() {
+    assert_eq!(Error::NoApiKeySet.to_string(), "no API key set");
+    assert_eq!(Error::Timeout.to_string(), "the request timed out");
+    assert_eq!(
+        Error::CouldNotParseUri("http://".to_string()).to_string(),
+        "could not parse the URI http://"
+    );
+    assert_eq!(
+        yandex::YandexError::InvalidAPIKey.to_string(),
+        "Yandex error 401 : InvalidAPIKey"
+    );
+    assert_eq!(
+        google_v2::GoogleV2Error::InvalidRequest.to_string(),
+        "Google v2 error 400 : InvalidRequest"
+    );
+    assert_eq!(
+        Error::GoogleV2APIError(google_v2::GoogleV2Error::RateLimitExceeded.into()).to_string(),
+        "Google v2 API error"
+    );
+}
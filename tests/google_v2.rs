@@ -0,0 +1,42 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+#[tokio::test]
+async fn google_v2_base_url() {
+    let server = MockServer::start(|req| {
+        if req.uri.starts_with("/v2/detect?") {
+            json(
+                200,
+                r#"{"data":{"detections":[[{"confidence":1,"isReliable":false,"language":"de"}]]}}"#,
+            )
+        } else {
+            json(
+                200,
+                r#"{"data":{"translations":[{"translatedText":"Hallo Welt"}]}}"#,
+            )
+        }
+    })
+    .await;
+    let base_url = format!("{}/v2", server.url);
+    let translator = GoogleV2::with_key("key").with_base_url(&base_url);
+
+    let res = translator
+        .translate(
+            "Hello world".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo Welt".to_string()));
+
+    let res = translator.detect("Hallo Welt".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::German)));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/v2?key=key");
+    assert_eq!(requests[1].uri, "/v2/detect?key=key");
+}
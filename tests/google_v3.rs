@@ -0,0 +1,198 @@
+mod common;
+
+use common::{json, MockServer, Recorded};
+use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use text_translator::*;
+
+const SERVICE_ACCOUNT: &str = include_str!("fixtures/service_account.json");
+const PUBLIC_KEY: &str = include_str!("fixtures/service_account_public.pem");
+
+const TRANSLATE_PATH: &str = "/v3/projects/text-translator-tests/locations/global:translateText";
+const DETECT_PATH: &str = "/v3/projects/text-translator-tests/locations/global:detectLanguage";
+
+/// Answers the token exchange with a token valid for `expires_in` seconds, and the API calls with fixed responses.
+fn handler(expires_in: u64) -> impl Fn(&Recorded) -> hyper::Response<hyper::Body> {
+    move |req| match req.uri.as_str() {
+        "/token" => json(
+            200,
+            &format!(
+                r#"{{"access_token":"ya29.token","expires_in":{},"token_type":"Bearer"}}"#,
+                expires_in
+            ),
+        ),
+        _ if req.header("authorization") != Some("Bearer ya29.token") => json(
+            401,
+            r#"{"error":{"code":401,"message":"Request had invalid authentication credentials.","status":"UNAUTHENTICATED"}}"#,
+        ),
+        TRANSLATE_PATH => json(
+            200,
+            r#"{"translations":[{"translatedText":"Hallo Welt","detectedLanguageCode":"en"}]}"#,
+        ),
+        DETECT_PATH => json(
+            200,
+            r#"{"languages":[{"languageCode":"zh-CN","confidence":0.98}]}"#,
+        ),
+        _ => json(
+            404,
+            r#"{"error":{"code":404,"message":"Not found","status":"NOT_FOUND"}}"#,
+        ),
+    }
+}
+
+fn translator(server: &MockServer) -> GoogleV3 {
+    GoogleV3::with_service_account(google_v3::ServiceAccount::from_json(SERVICE_ACCOUNT).unwrap())
+        .with_base_url(&format!("{}/v3", server.url))
+        .with_token_uri(&format!("{}/token", server.url))
+}
+
+#[tokio::test]
+async fn google_v3_translate_and_detect() {
+    let server = MockServer::start(handler(3600)).await;
+    let translator = translator(&server);
+
+    let res = translator
+        .translate(
+            "Hello world".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo Welt".to_string()));
+
+    let res = translator.detect("你好".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::Chinese)));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3, "the access token should be cached");
+    assert_eq!(requests[1].uri, TRANSLATE_PATH);
+    assert_eq!(
+        requests[1].body,
+        r#"{"contents":["Hello world"],"sourceLanguageCode":"en","targetLanguageCode":"de","mimeType":"text/plain"}"#
+    );
+    assert_eq!(requests[2].uri, DETECT_PATH);
+    assert_eq!(
+        requests[2].body,
+        r#"{"content":"你好","mimeType":"text/plain"}"#
+    );
+}
+
+#[tokio::test]
+async fn google_v3_signed_jwt() {
+    let server = MockServer::start(handler(3600)).await;
+    translator(&server)
+        .detect("Hello".to_string())
+        .await
+        .unwrap();
+
+    let token_request = &server.requests()[0];
+    assert_eq!(token_request.method, "POST");
+    assert_eq!(token_request.uri, "/token");
+
+    let mut params = token_request.body.split('&');
+    assert_eq!(
+        params.next(),
+        Some("grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer")
+    );
+    let assertion = params.next().unwrap().trim_start_matches("assertion=");
+
+    let token_uri = format!("{}/token", server.url);
+    let mut validation = Validation::new(Algorithm::RS256);
+    validation.set_audience(&[token_uri]);
+    let claims = decode::<serde_json::Value>(
+        assertion,
+        &DecodingKey::from_rsa_pem(PUBLIC_KEY.as_bytes()).unwrap(),
+        &validation,
+    )
+    .expect("the JWT should be signed with the service account key")
+    .claims;
+
+    assert_eq!(
+        claims["iss"],
+        "translator@text-translator-tests.iam.gserviceaccount.com"
+    );
+    assert_eq!(
+        claims["scope"],
+        "https://www.googleapis.com/auth/cloud-translation"
+    );
+}
+
+#[tokio::test]
+async fn google_v3_token_renewed_when_expired() {
+    // tokens expiring within the safety margin are never reused
+    let server = MockServer::start(handler(30)).await;
+    let translator = translator(&server);
+
+    translator.detect("Hello".to_string()).await.unwrap();
+    translator.detect("Hello".to_string()).await.unwrap();
+
+    let token_requests = server
+        .requests()
+        .iter()
+        .filter(|req| req.uri == "/token")
+        .count();
+    assert_eq!(token_requests, 2);
+}
+
+#[tokio::test]
+async fn google_v3_errors() {
+    let server = MockServer::start(handler(3600)).await;
+
+    let res = translator(&server)
+        .with_location("europe-west1")
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::GoogleV3APIError(
+            ProviderError::new(google_v3::GoogleV3Error::NotFound, 404)
+                .with_code("NOT_FOUND")
+                .with_message("Not found")
+        ))
+    );
+
+    let server = MockServer::start(|_| {
+        json(
+            400,
+            r#"{"error":"invalid_grant","error_description":"Invalid JWT Signature."}"#,
+        )
+    })
+    .await;
+    let res = translator(&server).detect("Hello".to_string()).await;
+    assert_eq!(
+        res,
+        Err(Error::GoogleV3APIError(
+            ProviderError::new(google_v3::GoogleV3Error::InvalidGrant, 400)
+                .with_code("invalid_grant")
+                .with_message("Invalid JWT Signature.")
+        ))
+    );
+
+    let res = GoogleV3::new().detect("Hello".to_string()).await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+}
+
+#[test]
+fn google_v3_service_account_file() {
+    let translator =
+        GoogleV3::from_service_account_file("tests/fixtures/service_account.json").unwrap();
+    assert_eq!(
+        translator.get_service_account().unwrap().project_id,
+        "text-translator-tests"
+    );
+
+    match GoogleV3::from_service_account_file("tests/fixtures/missing.json") {
+        Err(Error::InvalidCredentials(_)) => (),
+        other => panic!("expected InvalidCredentials, got {:?}", other),
+    }
+
+    // the parsing error is kept as the source
+    let err = google_v3::ServiceAccount::from_json(r#"{"type": "service_account"}"#).unwrap_err();
+    match &err {
+        Error::CouldNotParseCredentials(_) => (),
+        other => panic!("expected CouldNotParseCredentials, got {:?}", other),
+    }
+    let source = std::error::Error::source(&err).unwrap();
+    assert!(source.downcast_ref::<serde_json::Error>().is_some());
+    assert!(source.to_string().contains("missing field"));
+    assert!(err.is_auth());
+}
@@ -0,0 +1,116 @@
+#![cfg(feature = "keyless")]
+
+mod common;
+
+use common::{json, MockServer, Recorded};
+use text_translator::*;
+
+/// Decodes the `q` parameter of a form-encoded body.
+fn query_text(req: &Recorded) -> String {
+    let encoded = req.body.trim_start_matches("q=").as_bytes();
+    let mut bytes = Vec::new();
+    let mut i = 0;
+    while i < encoded.len() {
+        if encoded[i] == b'%' {
+            let hex = std::str::from_utf8(&encoded[i + 1..i + 3]).unwrap();
+            bytes.push(u8::from_str_radix(hex, 16).unwrap());
+            i += 3;
+        } else {
+            bytes.push(encoded[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(bytes).unwrap()
+}
+
+/// "Translates" the text to upper case, detected as French.
+fn upper_case(req: &Recorded) -> hyper::Response<hyper::Body> {
+    let text = query_text(req);
+    let response = serde_json::json!([[[text.to_uppercase(), text, null, null, 10]], null, "fr"]);
+    json(200, &response.to_string())
+}
+
+#[tokio::test]
+async fn google_web_translate() {
+    let server = MockServer::start(upper_case).await;
+    let translator = GoogleWeb::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Bonjour le monde".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::Chinese,
+        )
+        .await;
+    assert_eq!(res, Ok("BONJOUR LE MONDE".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/?client=gtx&sl=fr&tl=zh-CN&dt=t");
+    assert_eq!(requests[0].body, "q=Bonjour%20le%20monde");
+}
+
+#[tokio::test]
+async fn google_web_split_long_text() {
+    let server = MockServer::start(upper_case).await;
+    let translator = GoogleWeb::new()
+        .with_base_url(&server.url)
+        .with_max_chars(20);
+
+    let text = "Première phrase. Deuxième phrase assez longue !\n\nTroisième ligne\nunmotbeaucouptroplongpourunseulmorceau fin";
+    let res = translator
+        .translate(
+            text.to_string(),
+            InputLanguage::Automatic,
+            Language::English,
+        )
+        .await;
+    assert_eq!(res, Ok(text.to_uppercase()));
+
+    let requests = server.requests();
+    assert!(requests.len() > 1);
+    for req in &requests {
+        let chunk = query_text(req);
+        assert!(chunk.chars().count() <= 20, "chunk too long: {:?}", chunk);
+        assert_eq!(chunk, chunk.trim());
+    }
+    assert_eq!(query_text(&requests[0]), "Première phrase.");
+    assert_eq!(query_text(&requests[1]), "Deuxième phrase");
+}
+
+#[tokio::test]
+async fn google_web_detect() {
+    let server = MockServer::start(upper_case).await;
+    let translator = GoogleWeb::new()
+        .with_base_url(&server.url)
+        .with_max_chars(10);
+
+    let res = translator.detect("Bonjour tout le monde".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::French)));
+
+    // only the first chunk is sent
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(query_text(&requests[0]), "Bonjour");
+}
+
+#[tokio::test]
+async fn google_web_errors() {
+    let server = MockServer::start(|_| json(429, "")).await;
+
+    let res = GoogleWeb::new()
+        .with_base_url(&server.url)
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::GoogleWebAPIError(
+            google_web::GoogleWebError::TooManyRequests.into()
+        ))
+    );
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+#[tokio::test]
+async fn libretranslate_translate_without_key() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"detectedLanguage":{"confidence":90.0,"language":"en"},"translatedText":"¡Hola!"}"#,
+        )
+    })
+    .await;
+    let translator = LibreTranslate::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello!".to_string(),
+            InputLanguage::Automatic,
+            Language::Spanish,
+        )
+        .await;
+    assert_eq!(res, Ok("¡Hola!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/translate");
+    assert_eq!(
+        requests[0].body,
+        r#"{"q":"Hello!","source":"auto","target":"es","format":"text"}"#
+    );
+}
+
+#[tokio::test]
+async fn libretranslate_translate_with_key() {
+    let server = MockServer::start(|_| json(200, r#"{"translatedText":"Hallo!"}"#)).await;
+    let translator = LibreTranslate::with_key("secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello!".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo!".to_string()));
+    assert_eq!(
+        server.requests()[0].body,
+        r#"{"q":"Hello!","source":"en","target":"de","format":"text","api_key":"secret"}"#
+    );
+}
+
+#[tokio::test]
+async fn libretranslate_detect() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"[{"confidence":92.0,"language":"fr"},{"confidence":3.0,"language":"it"}]"#,
+        )
+    })
+    .await;
+    let translator = LibreTranslate::new().with_base_url(&server.url);
+
+    let res = translator.detect("Bonjour !".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::French)));
+    assert_eq!(server.requests()[0].uri, "/detect");
+    assert_eq!(server.requests()[0].body, r#"{"q":"Bonjour !"}"#);
+}
+
+#[tokio::test]
+async fn libretranslate_errors() {
+    let server = MockServer::start(|_| json(403, r#"{"error":"Invalid API key"}"#)).await;
+
+    let res = LibreTranslate::with_key("invalid")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::LibreTranslateAPIError(
+            ProviderError::new(libretranslate::LibreTranslateError::Forbidden, 403)
+                .with_message("Invalid API key")
+        ))
+    );
+}
+
+#[test]
+fn libretranslate_key_is_optional() {
+    assert!(!LibreTranslate::new().is_key_required());
+    assert!(DeepL::new().is_key_required());
+}
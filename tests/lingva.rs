@@ -0,0 +1,92 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+#[tokio::test]
+async fn lingva_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"translation":"Hallo Welt / zusammen","info":{"detectedSource":"en"}}"#,
+        )
+    })
+    .await;
+    let translator = Lingva::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello world / everyone".to_string(),
+            InputLanguage::Automatic,
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("Hallo Welt / zusammen".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    // the text is encoded as a single path segment
+    assert_eq!(
+        requests[0].uri,
+        "/api/v1/auto/de/Hello%20world%20%2F%20everyone"
+    );
+}
+
+#[tokio::test]
+async fn lingva_errors() {
+    let server = MockServer::start(|req| {
+        if req.uri.starts_with("/api/v1/en/mrj/") {
+            json(400, r#"{"error":"Invalid target language"}"#)
+        } else {
+            json(
+                500,
+                r#"{"error":"An error occurred while retrieving the translation"}"#,
+            )
+        }
+    })
+    .await;
+    let translator = Lingva::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::HillMari,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::LingvaAPIError(
+            ProviderError::new(lingva::LingvaError::BadRequest, 400)
+                .with_message("Invalid target language")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::LingvaAPIError(
+            ProviderError::new(lingva::LingvaError::TranslationFailed, 500)
+                .with_message("An error occurred while retrieving the translation")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::SameLanguages(Language::French, Language::French))
+    );
+}
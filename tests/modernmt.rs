@@ -0,0 +1,209 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::{
+    modernmt::{ModernMtError, ModernMtImportJob, ModernMtMemory},
+    *,
+};
+
+#[tokio::test]
+async fn modernmt_translate_with_context() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"status":200,"data":{"translation":"Ciao, mi chiamo Naruto!","contextVector":"1:0.75,2:0.25"}}"#,
+        )
+    })
+    .await;
+    let translator = ModernMt::with_key("key")
+        .with_base_url(&server.url)
+        .with_context_vector("1:0.75,2:0.25")
+        .with_hints(&[1, 2]);
+
+    let res = translator
+        .translate(
+            "Hello, my name is Naruto!".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Italian,
+        )
+        .await;
+    assert_eq!(res, Ok("Ciao, mi chiamo Naruto!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].uri,
+        "/translate?target=it&q=Hello%2C%20my%20name%20is%20Naruto%21&source=en&context_vector=1%3A0.75%2C2%3A0.25&hints=1%2C2"
+    );
+    assert_eq!(requests[0].header("MMT-ApiKey"), Some("key"));
+}
+
+#[tokio::test]
+async fn modernmt_detect_and_context_vector() {
+    let server = MockServer::start(|req| {
+        if req.uri.starts_with("/translate/detect") {
+            json(200, r#"{"status":200,"data":{"detectedLanguage":"zh-CN"}}"#)
+        } else {
+            json(
+                200,
+                r#"{"status":200,"data":{"source":"en","vectors":{"it":"3:0.5,1:0.2"}}}"#,
+            )
+        }
+    })
+    .await;
+    let translator = ModernMt::with_key("key").with_base_url(&server.url);
+
+    let res = translator.detect("你好".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::Chinese)));
+
+    let res = translator
+        .compute_context_vector("Believe it!", Language::English, Language::Italian)
+        .await;
+    assert_eq!(res, Ok(Some("3:0.5,1:0.2".to_string())));
+
+    let res = translator
+        .compute_context_vector("Believe it!", Language::English, Language::French)
+        .await;
+    assert_eq!(res, Ok(None));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].uri, "/translate/detect?q=%E4%BD%A0%E5%A5%BD");
+    assert_eq!(
+        requests[1].uri,
+        "/context-vector?source=en&targets=it&text=Believe%20it%21"
+    );
+}
+
+#[tokio::test]
+async fn modernmt_memories() {
+    let server = MockServer::start(|req| {
+        if req.uri == "/memories" {
+            json(
+                200,
+                r#"{"status":200,"data":{"id":42,"name":"Naruto","description":"Anime","creationDate":"2024-01-31T10:00:00+00:00"}}"#,
+            )
+        } else {
+            json(
+                200,
+                r#"{"status":200,"data":{"id":"00000000-0000-0000-0000-0000000379fc","memory":42,"size":1,"progress":0}}"#,
+            )
+        }
+    })
+    .await;
+    let translator = ModernMt::with_key("key").with_base_url(&server.url);
+
+    let memory = translator.create_memory("Naruto", Some("Anime")).await;
+    assert_eq!(
+        memory,
+        Ok(ModernMtMemory {
+            id: 42,
+            name: "Naruto".to_string(),
+            description: Some("Anime".to_string()),
+            creation_date: Some("2024-01-31T10:00:00+00:00".to_string()),
+        })
+    );
+
+    let job = ModernMtImportJob {
+        id: "00000000-0000-0000-0000-0000000379fc".to_string(),
+        memory: 42,
+        size: 1,
+        progress: 0.0,
+    };
+    let res = translator
+        .add_to_memory(
+            42,
+            Language::English,
+            Language::Italian,
+            "Believe it!",
+            "Puoi contarci!",
+        )
+        .await;
+    assert_eq!(res, Ok(job.clone()));
+
+    let tmx = r#"<tmx version="1.4"><body><tu><tuv xml:lang="en"><seg>Hi</seg></tuv><tuv xml:lang="it"><seg>Ciao</seg></tuv></tu></body></tmx>"#;
+    let res = translator.import_tmx(42, tmx.as_bytes()).await;
+    assert_eq!(res, Ok(job));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].body,
+        r#"{"name":"Naruto","description":"Anime"}"#
+    );
+    assert_eq!(requests[1].uri, "/memories/42/content");
+    assert_eq!(
+        requests[1].body,
+        r#"{"source":"en","target":"it","sentence":"Believe it!","translation":"Puoi contarci!"}"#
+    );
+
+    assert_eq!(requests[2].uri, "/memories/42/content");
+    let content_type = requests[2].header("Content-Type").unwrap();
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    let boundary = &content_type["multipart/form-data; boundary=".len()..];
+    assert!(requests[2].body.starts_with(&format!("--{}\r\n", boundary)));
+    assert!(requests[2]
+        .body
+        .contains("Content-Disposition: form-data; name=\"tmx\"; filename=\"memory.tmx\""));
+    assert!(requests[2]
+        .body
+        .ends_with(&format!("{}\r\n--{}--\r\n", tmx, boundary)));
+}
+
+#[tokio::test]
+async fn modernmt_errors() {
+    let server = MockServer::start(|req| {
+        if req.uri.contains("target=mrj") {
+            json(
+                400,
+                r#"{"status":400,"error":{"type":"UnsupportedLanguageException","message":"Language pair not supported: en > mrj"}}"#,
+            )
+        } else {
+            json(
+                404,
+                r#"{"status":404,"error":{"type":"MemoryNotFoundException","message":"Memory not found: 7"}}"#,
+            )
+        }
+    })
+    .await;
+    let translator = ModernMt::with_key("key").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::HillMari,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::ModernMtAPIError(
+            ProviderError::new(ModernMtError::UnsupportedLanguage, 400)
+                .with_code("UnsupportedLanguageException")
+                .with_message("Language pair not supported: en > mrj")
+        ))
+    );
+
+    let res = translator
+        .add_to_memory(7, Language::English, Language::Italian, "Hi", "Ciao")
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::ModernMtAPIError(
+            ProviderError::new(ModernMtError::NotFound, 404)
+                .with_code("MemoryNotFoundException")
+                .with_message("Memory not found: 7")
+        ))
+    );
+
+    let res = ModernMt::new()
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+    assert_eq!(server.requests().len(), 2);
+}
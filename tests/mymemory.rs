@@ -0,0 +1,136 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::{mymemory::*, *};
+
+const RESPONSE: &str = r#"{
+    "responseData": {"translatedText": "Ciao Mondo!", "match": 0.98},
+    "quotaFinished": false,
+    "responseDetails": "",
+    "responseStatus": 200,
+    "matches": [
+        {"id": "1", "segment": "Hello World!", "translation": "Ciao Mondo!", "quality": "74", "match": 1, "created-by": "MateCat"},
+        {"id": 0, "segment": "Hello World", "translation": "Ciao mondo", "quality": 70, "match": 0.85, "created-by": "MT!"}
+    ]
+}"#;
+
+#[tokio::test]
+async fn mymemory_translate() {
+    let server = MockServer::start(|_| json(200, RESPONSE)).await;
+    let translator = MyMemory::new()
+        .with_email("naruto@konoha.jp")
+        .with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello World!".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Italian,
+        )
+        .await;
+    assert_eq!(res, Ok("Ciao Mondo!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "GET");
+    assert_eq!(
+        requests[0].uri,
+        "/get?q=Hello%20World%21&langpair=en%7Cit&de=naruto%40konoha.jp"
+    );
+}
+
+#[tokio::test]
+async fn mymemory_match_quality() {
+    let server = MockServer::start(|_| json(200, RESPONSE)).await;
+    let translator = MyMemory::with_key("private-tm").with_base_url(&server.url);
+
+    let res = translator
+        .translate_with_matches(
+            "Hello World!".to_string(),
+            InputLanguage::Automatic,
+            Language::Chinese,
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.text, "Ciao Mondo!");
+    assert_eq!(res.match_quality, 0.98);
+    assert_eq!(
+        res.matches,
+        vec![
+            MyMemoryMatch {
+                segment: "Hello World!".to_string(),
+                translation: "Ciao Mondo!".to_string(),
+                match_quality: 1.0,
+                quality: Some(74),
+                created_by: Some("MateCat".to_string()),
+            },
+            MyMemoryMatch {
+                segment: "Hello World".to_string(),
+                translation: "Ciao mondo".to_string(),
+                match_quality: 0.85,
+                quality: Some(70),
+                created_by: Some("MT!".to_string()),
+            }
+        ]
+    );
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].uri,
+        "/get?q=Hello%20World%21&langpair=Autodetect%7Czh-CN&key=private-tm"
+    );
+}
+
+#[tokio::test]
+async fn mymemory_errors() {
+    let server = MockServer::start(|req| {
+        if req.uri.contains("langpair=en%7Cmrj") {
+            // errors are returned with a successful HTTP status
+            json(
+                200,
+                r#"{"responseData":{"translatedText":"'MRJ' IS AN INVALID TARGET LANGUAGE . EXAMPLE: LANGPAIR=EN|IT USING 2 LETTER ISO OR RFC3066 LIKE ZH-CN. ALMOST ALL LANGUAGES SUPPORTED BUT SOME MAY HAVE NO CONTENT","match":null},"responseStatus":"403","matches":""}"#,
+            )
+        } else {
+            json(
+                429,
+                r#"{"responseData":{"translatedText":"MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY."},"responseStatus":429}"#,
+            )
+        }
+    })
+    .await;
+    let translator = MyMemory::new().with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::HillMari,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::MyMemoryAPIError(
+            ProviderError::new(MyMemoryError::InvalidParameter, 200)
+                .with_code("403")
+                .with_message("'MRJ' IS AN INVALID TARGET LANGUAGE . EXAMPLE: LANGPAIR=EN|IT USING 2 LETTER ISO OR RFC3066 LIKE ZH-CN. ALMOST ALL LANGUAGES SUPPORTED BUT SOME MAY HAVE NO CONTENT")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::MyMemoryAPIError(
+            ProviderError::new(MyMemoryError::QuotaExceeded, 429)
+                .with_code("429")
+                .with_message(
+                    "MYMEMORY WARNING: YOU USED ALL AVAILABLE FREE TRANSLATIONS FOR TODAY."
+                )
+        ))
+    );
+}
@@ -0,0 +1,539 @@
+#![cfg(feature = "offline")]
+
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use text_translator::{offline::*, *};
+use zip::{write::FileOptions, ZipWriter};
+
+/// Returns a new empty directory, unique to the test.
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!(
+        "text-translator-offline-{}-{}",
+        std::process::id(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn metadata(from: &str, to: &str) -> String {
+    format!(
+        r#"{{"package_version":"1.0","argos_version":"1.0","from_code":"{}","from_name":"","to_code":"{}","to_name":""}}"#,
+        from, to
+    )
+}
+
+/// Writes an `.argosmodel` archive, with the same layout as the published packages.
+fn write_archive(dir: &Path, from: &str, to: &str) -> PathBuf {
+    let name = format!("translate-{}_{}-1_0", from, to);
+    let path = dir.join(format!("{}.argosmodel", name));
+    let mut zip = ZipWriter::new(File::create(&path).unwrap());
+
+    zip.add_directory(format!("{}/", name), FileOptions::default())
+        .unwrap();
+    zip.start_file(format!("{}/metadata.json", name), FileOptions::default())
+        .unwrap();
+    zip.write_all(metadata(from, to).as_bytes()).unwrap();
+    zip.start_file(format!("{}/model/model.bin", name), FileOptions::default())
+        .unwrap();
+    zip.write_all(b"weights").unwrap();
+    zip.finish().unwrap();
+
+    path
+}
+
+/// Writes an extracted package directory.
+fn write_dir(dir: &Path, from: &str, to: &str) -> PathBuf {
+    let path = dir.join(format!("translate-{}_{}", from, to));
+    fs::create_dir_all(&path).unwrap();
+    fs::write(path.join("metadata.json"), metadata(from, to)).unwrap();
+    path
+}
+
+/// Vocabulary of the tiny model, shared by the source and the target.
+const TINY_VOCABULARY: [&str; 7] = [
+    "<unk>",
+    "<s>",
+    "</s>",
+    "▁hello",
+    "▁world",
+    "▁bonjour",
+    "▁monde",
+];
+
+/// Index of the translation of each token of the vocabulary.
+const TINY_DICTIONARY: [usize; 7] = [0, 1, 2, 5, 6, 3, 4];
+
+/// Number of positions encoded by the tiny model.
+const TINY_POSITIONS: usize = 16;
+
+/// Number of position encodings of the tiny model, above the default maximum decoding length of beam search.
+const TINY_ENCODINGS: usize = 512;
+
+/// A variable of a CTranslate2 model.
+enum Variable {
+    Float(Vec<u32>, Vec<f32>),
+    Int8(i8),
+}
+
+/// Returns a `rows x cols` matrix, zero except at the given entries.
+fn matrix(
+    rows: usize,
+    cols: usize,
+    entries: impl Iterator<Item = (usize, usize, f32)>,
+) -> Variable {
+    let mut values = vec![0.0; rows * cols];
+    for (row, col, value) in entries {
+        values[row * cols + col] = value;
+    }
+    Variable::Float(vec![rows as u32, cols as u32], values)
+}
+
+fn vector(values: Vec<f32>) -> Variable {
+    Variable::Float(vec![values.len() as u32], values)
+}
+
+/// Writes a string in the format of the CTranslate2 model files.
+fn write_model_string(out: &mut Vec<u8>, string: &str) {
+    out.extend(&(string.len() as u16 + 1).to_le_bytes());
+    out.extend(string.as_bytes());
+    out.push(0);
+}
+
+/// Writes a tiny CTranslate2 Transformer model, translating word for word with `TINY_DICTIONARY`.
+///
+/// Tokens and positions are one-hot encoded in separate dimensions, all the other layers are empty except the
+/// encoder attention of the decoder: it copies the source token at the same position as the decoded token,
+/// which the output projection maps to its translation. The source ends with `</s>`, ending the translation.
+fn write_tiny_model(dir: &Path) {
+    let vocabulary = TINY_VOCABULARY.len();
+    let (token, copy, position) = (0, vocabulary, 2 * vocabulary);
+    let depth = 2 * vocabulary + TINY_POSITIONS;
+    let identity = |offset: usize, size: usize, value: f32| {
+        (0..size).map(move |i| (offset + i, offset + i, value))
+    };
+
+    let mut variables: Vec<(String, Variable)> = Vec::new();
+    for scope in &["encoder", "decoder"] {
+        let embeddings = match *scope {
+            "encoder" => "encoder/embeddings_0/weight",
+            _ => "decoder/embeddings/weight",
+        };
+        variables.push((
+            embeddings.to_string(),
+            matrix(
+                vocabulary,
+                depth,
+                (0..vocabulary).map(|i| (i, token + i, 1.0)),
+            ),
+        ));
+        variables.push((format!("{}/scale_embeddings", scope), Variable::Int8(0)));
+        variables.push((format!("{}/num_heads", scope), Variable::Int8(1)));
+        variables.push((
+            format!("{}/position_encodings/encodings", scope),
+            matrix(
+                TINY_ENCODINGS,
+                depth,
+                (0..TINY_POSITIONS).map(|i| (i, position + i, 1.0)),
+            ),
+        ));
+
+        let mut norms = vec![
+            format!("{}/layer_norm", scope),
+            format!("{}/layer_0/ffn/layer_norm", scope),
+        ];
+        norms.push(format!("{}/layer_0/self_attention/layer_norm", scope));
+        variables.push((
+            format!("{}/layer_0/self_attention/linear_0/weight", scope),
+            matrix(3 * depth, depth, None.into_iter()),
+        ));
+        variables.push((
+            format!("{}/layer_0/self_attention/linear_1/weight", scope),
+            matrix(depth, depth, None.into_iter()),
+        ));
+        variables.push((
+            format!("{}/layer_0/ffn/linear_0/weight", scope),
+            matrix(4, depth, None.into_iter()),
+        ));
+        variables.push((
+            format!("{}/layer_0/ffn/linear_1/weight", scope),
+            matrix(depth, 4, None.into_iter()),
+        ));
+
+        if *scope == "decoder" {
+            norms.push("decoder/layer_0/attention/layer_norm".to_string());
+            // queries and keys match the positions, values copy the source tokens
+            variables.push((
+                "decoder/layer_0/attention/linear_0/weight".to_string(),
+                matrix(depth, depth, identity(position, TINY_POSITIONS, 10.0)),
+            ));
+            variables.push((
+                "decoder/layer_0/attention/linear_1/weight".to_string(),
+                matrix(
+                    2 * depth,
+                    depth,
+                    identity(position, TINY_POSITIONS, 1.0)
+                        .chain((0..vocabulary).map(|i| (depth + copy + i, token + i, 1.0))),
+                ),
+            ));
+            variables.push((
+                "decoder/layer_0/attention/linear_2/weight".to_string(),
+                matrix(depth, depth, identity(copy, vocabulary, 1.0)),
+            ));
+            variables.push((
+                "decoder/projection/weight".to_string(),
+                matrix(
+                    vocabulary,
+                    depth,
+                    TINY_DICTIONARY
+                        .iter()
+                        .enumerate()
+                        .map(|(i, &to)| (to, copy + i, 10.0)),
+                ),
+            ));
+            variables.push((
+                "decoder/projection/bias".to_string(),
+                vector(vec![0.0; vocabulary]),
+            ));
+        }
+
+        for norm in norms {
+            variables.push((format!("{}/gamma", norm), vector(vec![1.0; depth])));
+            variables.push((format!("{}/beta", norm), vector(vec![0.0; depth])));
+        }
+    }
+
+    let mut model = Vec::new();
+    model.extend(&6u32.to_le_bytes());
+    write_model_string(&mut model, "TransformerSpec");
+    model.extend(&7u32.to_le_bytes());
+    model.extend(&(variables.len() as u32).to_le_bytes());
+    for (name, variable) in &variables {
+        write_model_string(&mut model, name);
+        match variable {
+            Variable::Float(shape, values) => {
+                model.push(shape.len() as u8);
+                shape
+                    .iter()
+                    .for_each(|dim| model.extend(&dim.to_le_bytes()));
+                model.push(0);
+                model.extend(&(values.len() as u32 * 4).to_le_bytes());
+                values
+                    .iter()
+                    .for_each(|value| model.extend(&value.to_le_bytes()));
+            }
+            Variable::Int8(value) => {
+                model.extend(&[0, 1]);
+                model.extend(&1u32.to_le_bytes());
+                model.push(*value as u8);
+            }
+        }
+    }
+    model.extend(&0u32.to_le_bytes());
+
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join("model.bin"), model).unwrap();
+    fs::write(
+        dir.join("config.json"),
+        r#"{"add_source_bos":false,"add_source_eos":true,"bos_token":"<s>","decoder_start_token":"<s>","eos_token":"</s>","unk_token":"<unk>"}"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.join("shared_vocabulary.json"),
+        serde_json::to_string(&TINY_VOCABULARY).unwrap(),
+    )
+    .unwrap();
+}
+
+/// Writes a length-delimited protobuf field.
+fn write_proto_bytes(out: &mut Vec<u8>, field: u8, bytes: &[u8]) {
+    assert!(bytes.len() < 128);
+    out.extend(&[field << 3 | 2, bytes.len() as u8]);
+    out.extend(bytes);
+}
+
+/// Writes a SentencePiece word model of the `TINY_VOCABULARY`, without text normalization.
+fn write_tiny_sentencepiece(path: &Path) {
+    let mut model = Vec::new();
+    for (i, piece) in TINY_VOCABULARY.iter().enumerate() {
+        // unknown, control or normal piece
+        let kind = match i {
+            0 => 2,
+            1 | 2 => 3,
+            _ => 1,
+        };
+        let mut message = Vec::new();
+        write_proto_bytes(&mut message, 1, piece.as_bytes());
+        message.extend(&[3 << 3, kind]);
+        write_proto_bytes(&mut model, 1, &message);
+    }
+    // word model type
+    write_proto_bytes(&mut model, 2, &[3 << 3, 3]);
+    let mut normalizer = Vec::new();
+    write_proto_bytes(&mut normalizer, 1, b"identity");
+    write_proto_bytes(&mut model, 3, &normalizer);
+
+    fs::write(path, model).unwrap();
+}
+
+/// Writes an extracted package running the tiny model.
+fn write_tiny_package(dir: &Path, from: &str, to: &str) -> PathBuf {
+    let path = write_dir(dir, from, to);
+    write_tiny_model(&path.join("model"));
+    write_tiny_sentencepiece(&path.join("sentencepiece.model"));
+    path
+}
+
+/// Tags the text with the language pair of the package, and records the calls.
+#[derive(Default, Clone)]
+struct TagBackend {
+    calls: Arc<Mutex<Vec<(Language, Language)>>>,
+}
+
+impl OfflineBackend for TagBackend {
+    fn translate(&self, package: &ArgosPackage, text: &str) -> Result<String, Error> {
+        self.calls.lock().unwrap().push((package.from, package.to));
+        Ok(format!(
+            "[{}>{}]{}",
+            package.from.to_language_code(),
+            package.to.to_language_code(),
+            text
+        ))
+    }
+}
+
+/// Takes a while to translate.
+struct SlowBackend;
+
+impl OfflineBackend for SlowBackend {
+    fn translate(&self, _package: &ArgosPackage, text: &str) -> Result<String, Error> {
+        std::thread::sleep(Duration::from_millis(300));
+        Ok(text.to_string())
+    }
+}
+
+#[test]
+fn offline_load_packages() {
+    let dir = test_dir("load");
+    write_archive(&dir, "fr", "en");
+    write_dir(&dir, "en", "de");
+    fs::write(dir.join("README.txt"), "not a package").unwrap();
+
+    let translator = Offline::from_dir(&dir).unwrap();
+    assert_eq!(
+        translator.installed_pairs(),
+        vec![
+            (Language::English, Language::German),
+            (Language::French, Language::English)
+        ]
+    );
+    assert!(translator.supports(Language::French, Language::German));
+    assert!(!translator.supports(Language::German, Language::French));
+
+    let archive = &translator.packages()[1];
+    assert!(archive.is_archive());
+    assert_eq!(archive.package_version.as_deref(), Some("1.0"));
+
+    let extracted = archive.extract_to(dir.join("extracted")).unwrap();
+    assert_eq!(extracted, dir.join("extracted/translate-fr_en-1_0"));
+    assert!(extracted.join("model/model.bin").is_file());
+    assert_eq!(
+        ArgosPackage::from_path(&extracted).unwrap().from,
+        Language::French
+    );
+
+    // an extracted archive is reused, and no temporary directory is left
+    fs::write(extracted.join("marker"), "").unwrap();
+    assert_eq!(
+        archive.extract_to(dir.join("extracted")).unwrap(),
+        extracted
+    );
+    assert!(extracted.join("marker").is_file());
+    assert_eq!(fs::read_dir(dir.join("extracted")).unwrap().count(), 1);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn offline_translate_direct_and_pivot() {
+    let dir = test_dir("translate");
+    let backend = TagBackend::default();
+    let translator = Offline::new()
+        .with_package_path(write_archive(&dir, "fr", "en"))
+        .unwrap()
+        .with_package_path(write_archive(&dir, "en", "de"))
+        .unwrap()
+        .with_package_path(write_dir(&dir, "fr", "es"))
+        .unwrap()
+        .with_backend(backend.clone());
+
+    let res = translator
+        .translate(
+            "Bonjour".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::Spanish,
+        )
+        .await;
+    assert_eq!(res, Ok("[fr>es]Bonjour".to_string()));
+
+    let res = translator
+        .translate(
+            "Bonjour".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::German,
+        )
+        .await;
+    assert_eq!(res, Ok("[en>de][fr>en]Bonjour".to_string()));
+
+    assert_eq!(
+        *backend.calls.lock().unwrap(),
+        vec![
+            (Language::French, Language::Spanish),
+            (Language::French, Language::English),
+            (Language::English, Language::German)
+        ]
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn offline_errors() {
+    let dir = test_dir("errors");
+    let translator = Offline::new()
+        .with_package_path(write_archive(&dir, "fr", "en"))
+        .unwrap();
+
+    // the archive does not contain a valid model
+    let res = translator
+        .translate(
+            "Bonjour".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::English,
+        )
+        .await;
+    match res {
+        Err(Error::OfflineError(OfflineError::InvalidPackage(_))) => (),
+        other => panic!("expected InvalidPackage, got {:?}", other),
+    }
+
+    let translator = translator.with_backend(TagBackend::default());
+    let res = translator
+        .translate(
+            "Bonjour".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::German,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::OfflineError(OfflineError::UnsupportedPair(
+            Language::French,
+            Language::German
+        )))
+    );
+
+    let res = translator
+        .translate(
+            "Bonjour".to_string(),
+            InputLanguage::Automatic,
+            Language::English,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::OfflineError(OfflineError::DetectionUnsupported))
+    );
+
+    fs::write(dir.join("broken.argosmodel"), "not a zip").unwrap();
+    match Offline::from_dir(&dir) {
+        Err(Error::OfflineError(OfflineError::InvalidPackage(_))) => (),
+        other => panic!("expected InvalidPackage, got {:?}", other),
+    }
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn offline_ctranslate2() {
+    let dir = test_dir("ctranslate2");
+    let translator = Offline::new()
+        .with_package_path(write_tiny_package(&dir, "en", "fr"))
+        .unwrap();
+
+    let res = translator
+        .translate(
+            "hello world\n\nworld hello world".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("bonjour monde\n\nmonde bonjour monde".to_string()));
+
+    // archives are extracted in the cache directory
+    let archive = dir.join("translate-en_fr.argosmodel");
+    let mut zip = ZipWriter::new(File::create(&archive).unwrap());
+    for file in &[
+        "metadata.json",
+        "sentencepiece.model",
+        "model/model.bin",
+        "model/config.json",
+        "model/shared_vocabulary.json",
+    ] {
+        zip.start_file(format!("translate-en_fr/{}", file), FileOptions::default())
+            .unwrap();
+        zip.write_all(&fs::read(dir.join("translate-en_fr").join(file)).unwrap())
+            .unwrap();
+    }
+    zip.finish().unwrap();
+
+    let translator = Offline::new()
+        .with_package_path(&archive)
+        .unwrap()
+        .with_backend(CTranslate2::new().with_cache_dir(dir.join("cache")));
+    let res = translator
+        .translate(
+            "world".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("monde".to_string()));
+    assert!(dir.join("cache/translate-en_fr/model/model.bin").is_file());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn offline_does_not_block_the_runtime() {
+    let dir = test_dir("blocking");
+    let translator = Offline::new()
+        .with_package_path(write_dir(&dir, "fr", "en"))
+        .unwrap()
+        .with_backend(SlowBackend);
+
+    // the other tasks of the runtime keep running while the model translates
+    let start = Instant::now();
+    let (res, timer) = futures::future::join(
+        translator.translate(
+            "Bonjour".to_string(),
+            InputLanguage::Defined(Language::French),
+            Language::English,
+        ),
+        async {
+            tokio::time::delay_for(Duration::from_millis(10)).await;
+            start.elapsed()
+        },
+    )
+    .await;
+    assert_eq!(res, Ok("Bonjour".to_string()));
+    assert!(timer < Duration::from_millis(250));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,93 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::{papago::PapagoError, *};
+
+#[tokio::test]
+async fn papago_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"message":{"result":{"srcLangType":"en","tarLangType":"ko","translatedText":"안녕하세요"}}}"#,
+        )
+    })
+    .await;
+    let translator =
+        Papago::with_credentials("client-id", "client-secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::Korean,
+        )
+        .await;
+    assert_eq!(res, Ok("안녕하세요".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/translation");
+    assert_eq!(
+        requests[0].header("X-NCP-APIGW-API-KEY-ID"),
+        Some("client-id")
+    );
+    assert_eq!(
+        requests[0].header("X-NCP-APIGW-API-KEY"),
+        Some("client-secret")
+    );
+    assert_eq!(requests[0].body, "source=auto&target=ko&text=Hello");
+}
+
+#[tokio::test]
+async fn papago_errors() {
+    let server = MockServer::start(|req| {
+        if req.body.contains("target=mrj") {
+            json(
+                400,
+                r#"{"errorMessage":"Unsupported target language","errorCode":"N2MT04"}"#,
+            )
+        } else {
+            json(
+                401,
+                r#"{"error":{"errorCode":"200","message":"Authentication Failed"}}"#,
+            )
+        }
+    })
+    .await;
+    let translator =
+        Papago::with_credentials("client-id", "client-secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::HillMari,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::PapagoAPIError(
+            ProviderError::new(PapagoError::UnsupportedTargetLanguage, 400)
+                .with_code("N2MT04")
+                .with_message("Unsupported target language")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::PapagoAPIError(
+            ProviderError::new(PapagoError::AuthenticationFailed, 401)
+                .with_code("200")
+                .with_message("Authentication Failed")
+        ))
+    );
+    assert_eq!(server.requests().len(), 2);
+}
@@ -0,0 +1,229 @@
+mod common;
+
+use std::io::{Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+const TRANSLATION: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+/// A request received by the proxy: its request line and `Proxy-Authorization` header.
+#[derive(Debug, Clone, PartialEq)]
+struct Proxied {
+    line: String,
+    authorization: Option<String>,
+}
+
+/// Starts a proxy opening the `CONNECT` tunnels, and answering the other requests itself.
+///
+/// Returns its URL and the requests received.
+fn start_proxy() -> (String, Arc<Mutex<Vec<Proxied>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let received = Arc::new(Mutex::new(Vec::new()));
+
+    let recorded = received.clone();
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let recorded = recorded.clone();
+            thread::spawn(move || handle(stream.unwrap(), recorded));
+        }
+    });
+
+    (url, received)
+}
+
+fn handle(mut stream: TcpStream, recorded: Arc<Mutex<Vec<Proxied>>>) {
+    let mut head = Vec::new();
+    let mut byte = [0; 1];
+    while !head.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap_or(0) == 1 {
+        head.push(byte[0]);
+    }
+    let head = String::from_utf8_lossy(&head).into_owned();
+
+    let mut lines = head.lines();
+    let line = lines.next().unwrap_or_default().to_string();
+    let authorization = lines
+        .filter_map(|header| header.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("proxy-authorization"))
+        .map(|(_, value)| value.trim().to_string());
+    recorded.lock().unwrap().push(Proxied {
+        line: line.clone(),
+        authorization,
+    });
+
+    if let Some(target) = line.strip_prefix("CONNECT ") {
+        let target = target.split(' ').next().unwrap();
+        let upstream = TcpStream::connect(target).unwrap();
+        let _ = stream.write_all(b"HTTP/1.1 200 Connection established\r\n\r\n");
+
+        let (mut from_client, mut to_upstream) =
+            (stream.try_clone().unwrap(), upstream.try_clone().unwrap());
+        thread::spawn(move || {
+            let _ = std::io::copy(&mut from_client, &mut to_upstream);
+            let _ = to_upstream.shutdown(Shutdown::Write);
+        });
+        let (mut from_upstream, mut to_client) = (upstream, stream);
+        let _ = std::io::copy(&mut from_upstream, &mut to_client);
+        let _ = to_client.shutdown(Shutdown::Write);
+    } else {
+        let _ = stream.write_all(
+            format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                TRANSLATION.len(),
+                TRANSLATION
+            )
+            .as_bytes(),
+        );
+    }
+}
+
+async fn translate(translator: Lingva<'_>) -> Result<String, Error> {
+    translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn proxy_https_tunnel() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let (proxy_url, received) = start_proxy();
+
+    // the credentials are percent-encoded in the URL
+    let proxy = Proxy::new(&proxy_url.replace("http://", "http://user:p%40ss@")).unwrap();
+    assert_eq!(proxy.get_username(), Some("user"));
+    let transport =
+        HyperTransport::with_root_certificates(server.certificate.as_ref().unwrap().as_bytes())
+            .unwrap()
+            .with_proxy(proxy);
+    let client = HttpClient::with_transport(transport);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+
+    let authority = server.url.trim_start_matches("https://");
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![Proxied {
+            line: format!("CONNECT {} HTTP/1.1", authority),
+            authorization: Some(format!("Basic {}", base64::encode("user:p@ss"))),
+        }]
+    );
+    // the credentials of the proxy are not sent to the API
+    assert_eq!(server.requests()[0].header("proxy-authorization"), None);
+}
+
+#[tokio::test]
+async fn proxy_untrusted_certificate() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+
+    let client = HttpClient::with_transport(HyperTransport::new().without_proxy());
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    match translate(translator).await {
+        Err(Error::TransportError(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn proxy_no_proxy() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let (proxy_url, received) = start_proxy();
+
+    let proxy = Proxy::new(&proxy_url)
+        .unwrap()
+        .with_no_proxy("example.com, localhost");
+    let transport =
+        HyperTransport::with_root_certificates(server.certificate.as_ref().unwrap().as_bytes())
+            .unwrap()
+            .with_proxy(proxy);
+    let client = HttpClient::with_transport(transport);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+    assert!(received.lock().unwrap().is_empty());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn proxy_plain_http() {
+    let (proxy_url, received) = start_proxy();
+
+    let proxy = Proxy::new(&proxy_url)
+        .unwrap()
+        .with_basic_auth("user", "password");
+    let client = HttpClient::with_transport(HyperTransport::new().with_proxy(proxy));
+    let translator = Lingva::new()
+        .with_base_url("http://lingva.example.com")
+        .with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+
+    // the request is sent to the proxy with its absolute URL
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert!(received[0]
+        .line
+        .starts_with("GET http://lingva.example.com/"));
+    assert_eq!(
+        received[0].authorization,
+        Some(format!("Basic {}", base64::encode("user:password")))
+    );
+}
+
+#[test]
+fn proxy_rules() {
+    let proxy = Proxy::new("proxy.example.com:3128")
+        .unwrap()
+        .with_no_proxy("LOCALHOST,.internal.example.com  *.corp.example.com, 10.0.0.0/8,::1");
+    assert_eq!(proxy.get_uri(), "http://proxy.example.com:3128/");
+    assert_eq!(proxy.get_username(), None);
+
+    let proxied = |url: &str| proxy.is_proxied(&url.parse().unwrap());
+    assert!(proxied("https://api.deepl.com/v2/translate"));
+    assert!(proxied("http://example.com"));
+    assert!(!proxied("http://localhost:8080"));
+    assert!(!proxied("https://internal.example.com"));
+    assert!(!proxied("https://mt.internal.example.com"));
+    assert!(proxied("https://notinternal.example.com"));
+    assert!(!proxied("https://mt.corp.example.com"));
+    assert!(!proxied("http://10.1.2.3:5000"));
+    assert!(proxied("http://11.1.2.3:5000"));
+    assert!(!proxied("http://[::1]:5000"));
+
+    let proxy = Proxy::new("http://proxy:3128").unwrap().with_no_proxy("*");
+    assert!(!proxy.is_proxied(&"https://api.deepl.com".parse().unwrap()));
+
+    assert!(matches!(
+        Proxy::new("http://proxy host"),
+        Err(Error::CouldNotParseUri(_))
+    ));
+}
+
+#[test]
+fn proxy_invalid_certificates() {
+    assert!(matches!(
+        HyperTransport::with_root_certificates(b"not a certificate"),
+        Err(Error::TlsError(_))
+    ));
+    assert!(matches!(
+        HyperTransport::with_root_certificates(
+            b"-----BEGIN CERTIFICATE-----\nbm90IGEgY2VydGlmaWNhdGU=\n-----END CERTIFICATE-----\n"
+        ),
+        Err(Error::TlsError(_))
+    ));
+}
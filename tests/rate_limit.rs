@@ -0,0 +1,202 @@
+use std::time::{Duration, Instant};
+
+use text_translator::*;
+
+/// Returns a client answering every translation in memory.
+fn client() -> (HttpClient, MemoryTransport) {
+    let transport = MemoryTransport::with_response(
+        200,
+        r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#,
+    );
+
+    (HttpClient::with_transport(transport.clone()), transport)
+}
+
+async fn translate<A: Api>(translator: &A, text: &str) -> Result<String, Error> {
+    translator
+        .translate(text.to_string(), InputLanguage::Automatic, Language::French)
+        .await
+}
+
+#[tokio::test]
+async fn rate_limit_requests() {
+    let (client, transport) = client();
+    let translator =
+        RateLimited::with_api(Lingva::new().with_client(&client)).with_requests_per_second(20.);
+
+    // the first second of budget is sent at once, the next requests wait for it to be refilled
+    let start = Instant::now();
+    let results = futures::future::join_all((0..30).map(|_| translate(&translator, "Hello"))).await;
+
+    assert!(start.elapsed() >= Duration::from_millis(450));
+    assert!(results.iter().all(|res| res == &Ok("Bonjour".to_string())));
+    assert_eq!(transport.requests().len(), 30);
+}
+
+#[tokio::test]
+async fn rate_limit_characters() {
+    let (client, _) = client();
+    let translator =
+        RateLimited::with_api(Lingva::new().with_client(&client)).with_characters_per_second(1000.);
+    let text = "a".repeat(500);
+
+    let start = Instant::now();
+    translate(&translator, &text).await.unwrap();
+    translate(&translator, &text).await.unwrap();
+    assert!(start.elapsed() < Duration::from_millis(250));
+
+    translate(&translator, &text).await.unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(450));
+}
+
+#[tokio::test]
+async fn rate_limit_shared_between_clones() {
+    let (client, _) = client();
+    let translator =
+        RateLimited::with_api(Lingva::new().with_client(&client)).with_requests_per_second(10.);
+    let clone = translator.clone();
+
+    let start = Instant::now();
+    for _ in 0..10 {
+        translate(&translator, "Hello").await.unwrap();
+    }
+    translate(&clone, "Hello").await.unwrap();
+
+    assert!(start.elapsed() >= Duration::from_millis(90));
+    assert_eq!(translator.get_usage().requests, 11);
+}
+
+#[tokio::test]
+async fn rate_limit_usage() {
+    let transport = MemoryTransport::with_response(500, "{}");
+    let client = HttpClient::with_transport(transport);
+    let translator = RateLimited::with_api(Lingva::new().with_client(&client));
+
+    // the failed requests are counted too
+    assert!(translate(&translator, "Hello").await.is_err());
+    assert!(translate(&translator, "Grüß Gott").await.is_err());
+
+    let usage = translator.get_usage();
+    assert_eq!(
+        usage,
+        rate_limit::Usage {
+            requests: 2,
+            characters: 14,
+        }
+    );
+
+    // the usage can be saved, and restored by the next run
+    let saved = serde_json::to_string(&usage).unwrap();
+    assert_eq!(saved, r#"{"requests":2,"characters":14}"#);
+
+    let translator = RateLimited::with_api(Lingva::new().with_client(&client))
+        .with_usage(serde_json::from_str(&saved).unwrap());
+    assert!(translate(&translator, "Hello").await.is_err());
+    assert_eq!(translator.get_usage().requests, 3);
+
+    assert_eq!(translator.reset_usage().characters, 19);
+    assert_eq!(translator.get_usage(), rate_limit::Usage::default());
+}
+
+#[tokio::test]
+async fn rate_limit_detect() {
+    let transport = MemoryTransport::with_response(
+        200,
+        r#"{"data":{"detections":[[{"confidence":1,"isReliable":true,"language":"fr"}]]}}"#,
+    );
+    let client = HttpClient::with_transport(transport);
+    let translator = RateLimited::with_api(GoogleV2::with_key("key").with_client(&client));
+
+    assert_eq!(
+        translator.detect("Bonjour".to_string()).await,
+        Ok(Some(Language::French))
+    );
+    assert_eq!(translator.get_usage().characters, 7);
+}
+
+#[tokio::test]
+async fn rate_limit_batch() {
+    // an engine translating the texts one by one takes a request token for each of them
+    let (client, transport) = client();
+    let translator =
+        RateLimited::with_api(Lingva::new().with_client(&client)).with_requests_per_second(2.);
+
+    let start = Instant::now();
+    let res = translator
+        .translate_batch(
+            vec!["Hello".to_string(), "Hi".to_string(), "Hey".to_string()],
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok(vec!["Bonjour".to_string(); 3]));
+    assert!(start.elapsed() >= Duration::from_millis(450));
+
+    let usage = translator.get_usage();
+    assert_eq!(transport.requests().len() as u64, usage.requests);
+    assert_eq!(usage.characters, 10);
+}
+
+#[tokio::test]
+async fn rate_limit_batch_requests() {
+    let transport = MemoryTransport::new(|request| {
+        let body: serde_json::Value = serde_json::from_str(&request.body_text()).unwrap();
+        let translations = body["q"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|q| serde_json::json!({ "translatedText": q }))
+            .collect::<Vec<_>>();
+
+        Ok(http::Response::new(
+            serde_json::json!({ "data": { "translations": translations } })
+                .to_string()
+                .into_bytes(),
+        ))
+    });
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = RateLimited::with_api(GoogleV2::with_key("key").with_client(&client));
+
+    // a batch sent in several requests by the engine takes a request token for each of them
+    let texts: Vec<String> = (0..300).map(|i| format!("text {}", i)).collect();
+    let res = translator
+        .translate_batch(texts.clone(), InputLanguage::Automatic, Language::French)
+        .await;
+    assert_eq!(res, Ok(texts.clone()));
+
+    let usage = translator.get_usage();
+    assert_eq!(transport.requests().len(), 3);
+    assert_eq!(transport.requests().len() as u64, usage.requests);
+    assert_eq!(
+        usage.characters,
+        texts.iter().map(|text| text.len() as u64).sum::<u64>()
+    );
+}
+
+#[test]
+fn rate_limit_invalid_rates() {
+    for rate in [0., -1., f64::NAN, f64::INFINITY] {
+        let requests = std::panic::catch_unwind(|| {
+            RateLimited::with_api(Lingva::new()).with_requests_per_second(rate)
+        });
+        assert!(requests.is_err(), "rate {} accepted", rate);
+
+        let characters = std::panic::catch_unwind(|| {
+            RateLimited::with_api(Lingva::new()).with_characters_per_second(rate)
+        });
+        assert!(characters.is_err(), "rate {} accepted", rate);
+    }
+}
+
+#[tokio::test]
+async fn rate_limit_tiny_rate() {
+    let (client, transport) = client();
+    let translator =
+        RateLimited::with_api(Lingva::new().with_client(&client)).with_requests_per_second(1e-300);
+
+    // the request waits for its token, without overflowing the timer
+    let res =
+        tokio::time::timeout(Duration::from_millis(50), translate(&translator, "Hello")).await;
+    assert!(res.is_err());
+    assert!(transport.requests().is_empty());
+}
@@ -0,0 +1,276 @@
+mod common;
+
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use common::{json, MockServer, Recorded};
+use hyper::{Body, Response};
+use text_translator::*;
+
+const TRANSLATION: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+/// Fast policy, so that the tests do not wait.
+const POLICY: RetryPolicy = RetryPolicy::new()
+    .with_base_delay(Duration::from_millis(10))
+    .with_jitter(false);
+
+fn transport_error(kind: io::ErrorKind) -> Error {
+    Error::TransportError(ErrorSource::new(io::Error::new(kind, "transport error")))
+}
+
+/// Answers the first `failures` requests with the given response, then translates.
+fn failing(
+    failures: usize,
+    failure: impl Fn() -> Response<Body> + Send + Sync + 'static,
+) -> impl Fn(&Recorded) -> Response<Body> {
+    let count = AtomicUsize::new(0);
+
+    move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) < failures {
+            failure()
+        } else {
+            json(200, TRANSLATION)
+        }
+    }
+}
+
+async fn translate(translator: Lingva<'_>) -> Result<String, Error> {
+    translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn retry_until_success() {
+    let server = MockServer::start(failing(2, || {
+        json(503, r#"{"error":"Service Unavailable"}"#)
+    }))
+    .await;
+    let client = HttpClient::new().with_retry(POLICY);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+    assert_eq!(server.requests().len(), 3);
+
+    // the requests are sent again as is
+    let requests = server.requests();
+    assert!(requests.iter().all(|req| req.uri == requests[0].uri));
+}
+
+#[tokio::test]
+async fn retry_attempts_exhausted() {
+    let server =
+        MockServer::start(failing(5, || json(429, r#"{"error":"Too Many Requests"}"#))).await;
+    let client = HttpClient::new().with_retry(POLICY.with_max_attempts(4));
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    // the last response is handled by the engine
+    assert_eq!(
+        translate(translator).await,
+        Err(Error::LingvaAPIError(
+            ProviderError::new(lingva::LingvaError::TooManyRequests, 429)
+                .with_message("Too Many Requests")
+        ))
+    );
+    assert_eq!(server.requests().len(), 4);
+}
+
+#[tokio::test]
+async fn retry_not_retryable() {
+    let server = MockServer::start(failing(1, || {
+        json(400, r#"{"error":"Invalid target language"}"#)
+    }))
+    .await;
+    let client = HttpClient::new().with_retry(POLICY);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    assert_eq!(
+        translate(translator).await,
+        Err(Error::LingvaAPIError(
+            ProviderError::new(lingva::LingvaError::BadRequest, 400)
+                .with_message("Invalid target language")
+        ))
+    );
+    assert_eq!(server.requests().len(), 1);
+
+    // without policy, retryable errors are not retried either
+    let server = MockServer::start(failing(1, || json(503, "{}"))).await;
+    let translator = Lingva::new().with_base_url(&server.url);
+
+    assert!(translate(translator).await.is_err());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn retry_after() {
+    let server = MockServer::start(failing(1, || {
+        Response::builder()
+            .status(429)
+            .header("retry-after", "1")
+            .body(Body::from("{}"))
+            .unwrap()
+    }))
+    .await;
+    let client = HttpClient::new().with_retry(POLICY);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    let start = Instant::now();
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+    assert!(start.elapsed() >= Duration::from_secs(1));
+    assert_eq!(server.requests().len(), 2);
+
+    // a delay longer than the maximum one is not waited
+    let server = MockServer::start(failing(1, || {
+        Response::builder()
+            .status(503)
+            .header("retry-after", "3600")
+            .body(Body::from("{}"))
+            .unwrap()
+    }))
+    .await;
+    let client = HttpClient::new().with_retry(POLICY);
+    let translator = Lingva::new()
+        .with_base_url(&server.url)
+        .with_client(&client);
+
+    assert_eq!(
+        translate(translator).await,
+        Err(Error::LingvaAPIError(
+            lingva::LingvaError::UnknownErrorCode(503).into()
+        ))
+    );
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn retry_transport_errors() {
+    let count = AtomicUsize::new(0);
+    let transport = MemoryTransport::new(move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err(transport_error(io::ErrorKind::ConnectionReset))
+        } else {
+            Ok(Response::new(TRANSLATION.as_bytes().to_vec()))
+        }
+    });
+    let client = HttpClient::with_transport(transport.clone()).with_retry(POLICY);
+    let translator = Lingva::new().with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+    assert_eq!(transport.requests().len(), 3);
+
+    // the retryable errors can be chosen
+    let transport =
+        MemoryTransport::new(|_| Err(transport_error(io::ErrorKind::ConnectionRefused)));
+    let client = HttpClient::with_transport(transport.clone())
+        .with_retry(POLICY.with_retryable_errors(|_| false));
+    let translator = Lingva::new().with_client(&client);
+
+    match translate(translator).await {
+        Err(Error::TransportError(_)) => (),
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert_eq!(transport.requests().len(), 1);
+
+    // the requests that could not be built are not retried
+    let transport =
+        MemoryTransport::new(|_| Err(Error::RequestError("invalid header".to_string())));
+    let client = HttpClient::with_transport(transport.clone()).with_retry(POLICY);
+    let translator = Lingva::new().with_client(&client);
+
+    assert_eq!(
+        translate(translator).await,
+        Err(Error::RequestError("invalid header".to_string()))
+    );
+    assert_eq!(transport.requests().len(), 1);
+}
+
+/// Answers the first `failures` requests with the given Baidu error, then translates.
+fn baidu_failing(failures: usize, error: &'static str) -> impl Fn(&Recorded) -> Response<Body> {
+    let count = AtomicUsize::new(0);
+
+    move |_| {
+        if count.fetch_add(1, Ordering::SeqCst) < failures {
+            json(200, error)
+        } else {
+            json(
+                200,
+                r#"{"from":"en","to":"fr","trans_result":[{"src":"Hello","dst":"Bonjour"}]}"#,
+            )
+        }
+    }
+}
+
+async fn translate_baidu(server: &MockServer, client: &HttpClient) -> Result<String, Error> {
+    Baidu::with_credentials("app-id", "secret")
+        .with_base_url(&server.url)
+        .with_client(client)
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn retry_errors_of_successful_responses() {
+    // the API reports a rate limiting with a 200 status code
+    let limited = include_str!("fixtures/errors/baidu/access_frequency_limited.json");
+    let server = MockServer::start(baidu_failing(2, limited)).await;
+    let client = HttpClient::new().with_retry(POLICY);
+
+    assert_eq!(
+        translate_baidu(&server, &client).await,
+        Ok("Bonjour".to_string())
+    );
+    assert_eq!(server.requests().len(), 3);
+
+    // the permanent errors are not retried
+    let server = MockServer::start(baidu_failing(
+        1,
+        include_str!("fixtures/errors/baidu/unauthorized_user.json"),
+    ))
+    .await;
+
+    assert!(translate_baidu(&server, &client)
+        .await
+        .unwrap_err()
+        .is_auth());
+    assert_eq!(server.requests().len(), 1);
+
+    // nor the ones the policy does not retry
+    let server = MockServer::start(baidu_failing(1, limited)).await;
+    let client = HttpClient::new().with_retry(POLICY.with_retryable_errors(|_| false));
+
+    assert!(translate_baidu(&server, &client)
+        .await
+        .unwrap_err()
+        .is_retryable());
+    assert_eq!(server.requests().len(), 1);
+}
+
+#[test]
+fn retry_backoff() {
+    let policy = RetryPolicy::new()
+        .with_base_delay(Duration::from_millis(100))
+        .with_max_delay(Duration::from_secs(1));
+
+    assert_eq!(policy.backoff(1), Duration::from_millis(100));
+    assert_eq!(policy.backoff(2), Duration::from_millis(200));
+    assert_eq!(policy.backoff(4), Duration::from_millis(800));
+    assert_eq!(policy.backoff(5), Duration::from_secs(1));
+    assert_eq!(policy.backoff(100), Duration::from_secs(1));
+}
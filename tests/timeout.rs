@@ -0,0 +1,157 @@
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc, Arc,
+};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use text_translator::*;
+
+const TRANSLATION: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+/// Starts a raw HTTP server handling each connection in a thread, and returns its URL.
+fn start<F>(handler: F) -> String
+where
+    F: Fn(TcpStream) + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let handler = Arc::new(handler);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let handler = handler.clone();
+            thread::spawn(move || handler(stream.unwrap()));
+        }
+    });
+
+    url
+}
+
+/// Reads the headers of a request without body.
+fn read_request(stream: &mut TcpStream) {
+    let mut request = Vec::new();
+    let mut byte = [0; 1];
+    while !request.ends_with(b"\r\n\r\n") && stream.read(&mut byte).unwrap_or(0) == 1 {
+        request.push(byte[0]);
+    }
+}
+
+fn headers(content_length: usize) -> String {
+    format!(
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n",
+        content_length
+    )
+}
+
+async fn translate(translator: Lingva<'_>) -> Result<String, Error> {
+    translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn timeout_read_headers() {
+    // the server never answers
+    let url = start(|mut stream| {
+        read_request(&mut stream);
+        thread::sleep(Duration::from_secs(5));
+    });
+    let client =
+        HttpClient::with_timeouts(Timeouts::none().with_read_timeout(Duration::from_millis(200)));
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+
+    let start = Instant::now();
+    assert_eq!(translate(translator).await, Err(Error::Timeout));
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn timeout_read_body() {
+    // the server stops in the middle of the body
+    let url = start(|mut stream| {
+        read_request(&mut stream);
+        let _ = stream.write_all(headers(TRANSLATION.len()).as_bytes());
+        let _ = stream.write_all(&TRANSLATION.as_bytes()[..10]);
+        thread::sleep(Duration::from_secs(5));
+    });
+    let client =
+        HttpClient::with_timeouts(Timeouts::none().with_read_timeout(Duration::from_millis(200)));
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+
+    assert_eq!(translate(translator).await, Err(Error::Timeout));
+}
+
+#[tokio::test]
+async fn timeout_total() {
+    // the server sends the body slowly, each chunk within the read timeout
+    let url = start(|mut stream| {
+        read_request(&mut stream);
+        let _ = stream.write_all(headers(TRANSLATION.len()).as_bytes());
+        for byte in TRANSLATION.as_bytes() {
+            let _ = stream.write_all(&[*byte]);
+            let _ = stream.flush();
+            thread::sleep(Duration::from_millis(10));
+        }
+    });
+    let timeouts = Timeouts::none().with_read_timeout(Duration::from_millis(200));
+
+    let client = HttpClient::with_timeouts(timeouts);
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+
+    let client = HttpClient::with_timeouts(timeouts.with_total_timeout(Duration::from_millis(300)));
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+    let start = Instant::now();
+    assert_eq!(translate(translator).await, Err(Error::Timeout));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[tokio::test]
+async fn timeout_retried() {
+    // the first connection never answers
+    let connections = AtomicUsize::new(0);
+    let url = start(move |mut stream| {
+        read_request(&mut stream);
+        if connections.fetch_add(1, Ordering::SeqCst) == 0 {
+            thread::sleep(Duration::from_secs(5));
+        } else {
+            let _ = stream.write_all(headers(TRANSLATION.len()).as_bytes());
+            let _ = stream.write_all(TRANSLATION.as_bytes());
+        }
+    });
+    let client =
+        HttpClient::with_timeouts(Timeouts::none().with_total_timeout(Duration::from_millis(200)))
+            .with_retry(RetryPolicy::new().with_base_delay(Duration::from_millis(10)));
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+
+    assert_eq!(translate(translator).await, Ok("Bonjour".to_string()));
+}
+
+// the connection is closed by a background task, running while the test waits
+#[tokio::test(threaded_scheduler)]
+async fn timeout_cancel_on_drop() {
+    // the server reports when the client closes the connection
+    let (closed, is_closed) = mpsc::channel();
+    let url = start(move |mut stream| {
+        read_request(&mut stream);
+        let mut buffer = [0; 1];
+        if let Ok(0) = stream.read(&mut buffer) {
+            let _ = closed.send(());
+        }
+    });
+    let client = HttpClient::new();
+    let translator = Lingva::new().with_base_url(&url).with_client(&client);
+
+    // the request is dropped before getting a response
+    let res = tokio::time::timeout(Duration::from_millis(200), translate(translator)).await;
+    assert!(res.is_err());
+
+    assert_eq!(is_closed.recv_timeout(Duration::from_secs(2)), Ok(()));
+}
@@ -0,0 +1,111 @@
+// run with `--no-default-features --features rustls` to test the rustls backend
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+const TRANSLATION: &str = r#"{"translation":"Bonjour","info":{"detectedSource":"en"}}"#;
+
+async fn translate(client: &HttpClient, base_url: &str) -> Result<String, Error> {
+    Lingva::new()
+        .with_base_url(base_url)
+        .with_client(client)
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await
+}
+
+#[tokio::test]
+async fn tls_root_certificates() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let certificate = server.certificate.clone().unwrap();
+
+    let transport = HyperTransport::with_root_certificates(certificate.as_bytes())
+        .unwrap()
+        .without_proxy();
+    let client = HttpClient::with_transport(transport);
+
+    assert_eq!(
+        translate(&client, &server.url).await,
+        Ok("Bonjour".to_string())
+    );
+    assert_eq!(
+        translate(&client, &server.url).await,
+        Ok("Bonjour".to_string())
+    );
+    assert_eq!(server.requests().len(), 2);
+    assert_eq!(server.connections(), 1);
+}
+
+#[tokio::test]
+async fn tls_untrusted_root() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+
+    let client = HttpClient::with_transport(HyperTransport::new().without_proxy());
+
+    match translate(&client, &server.url).await {
+        Err(Error::TransportError(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[tokio::test]
+async fn tls_wrong_host() {
+    // the certificate is only valid for `localhost`
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let certificate = server.certificate.clone().unwrap();
+
+    let transport = HyperTransport::with_root_certificates(certificate.as_bytes())
+        .unwrap()
+        .without_proxy();
+    let client = HttpClient::with_transport(transport);
+
+    match translate(&client, &server.url.replace("localhost", "127.0.0.1")).await {
+        Err(Error::TransportError(_)) => {}
+        res => panic!("unexpected result: {:?}", res),
+    }
+    assert!(server.requests().is_empty());
+}
+
+#[cfg(feature = "native-tls")]
+#[tokio::test]
+async fn tls_native_tls_connector() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let certificate = server.certificate.clone().unwrap();
+
+    let tls = native_tls::TlsConnector::builder()
+        .add_root_certificate(native_tls::Certificate::from_pem(certificate.as_bytes()).unwrap())
+        .build()
+        .unwrap();
+    let client =
+        HttpClient::with_transport(HyperTransport::from_tls_connector(tls).without_proxy());
+
+    assert_eq!(
+        translate(&client, &server.url).await,
+        Ok("Bonjour".to_string())
+    );
+}
+
+#[cfg(feature = "rustls")]
+#[tokio::test]
+async fn tls_rustls_config() {
+    let server = MockServer::start_tls(|_| json(200, TRANSLATION)).await;
+    let certificate = server.certificate.clone().unwrap();
+
+    let mut config = rustls::ClientConfig::new();
+    config
+        .root_store
+        .add_pem_file(&mut certificate.as_bytes())
+        .unwrap();
+    let client =
+        HttpClient::with_transport(HyperTransport::from_tls_config(config).without_proxy());
+
+    assert_eq!(
+        translate(&client, &server.url).await,
+        Ok("Bonjour".to_string())
+    );
+}
@@ -0,0 +1,164 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use text_translator::*;
+
+/// Builds a response with the given status code and body.
+fn response(status: u16, body: &str) -> Result<http::Response<Vec<u8>>, Error> {
+    Ok(http::Response::builder()
+        .status(status)
+        .body(body.as_bytes().to_vec())
+        .unwrap())
+}
+
+#[tokio::test]
+async fn memory_transport_google_v2() {
+    let transport = MemoryTransport::new(|req| {
+        if req.uri.contains("/detect?") {
+            response(
+                200,
+                r#"{"data":{"detections":[[{"confidence":1,"isReliable":true,"language":"de"}]]}}"#,
+            )
+        } else {
+            response(
+                200,
+                r#"{"data":{"translations":[{"translatedText":"Bonjour"}]}}"#,
+            )
+        }
+    });
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("Bonjour".to_string()));
+
+    let res = translator.detect("Hallo".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::German)));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].uri,
+        "https://translation.googleapis.com/language/translate/v2?key=key"
+    );
+    assert_eq!(
+        requests[0].body_text(),
+        r#"{"q":"Hello","source":"en","target":"fr","format":"text"}"#
+    );
+    assert_eq!(
+        requests[1].uri,
+        "https://translation.googleapis.com/language/translate/v2/detect?key=key"
+    );
+}
+
+#[tokio::test]
+#[allow(deprecated)]
+async fn memory_transport_yandex() {
+    let transport = MemoryTransport::new(|req| {
+        if req.uri.contains("/detect?") {
+            response(200, r#"{"code":200,"lang":"de"}"#)
+        } else {
+            response(200, r#"{"code":200,"lang":"en-fr","text":["Bonjour"]}"#)
+        }
+    });
+    let client = HttpClient::with_transport(transport.clone());
+    let translator = Yandex::with_key("key").with_client(&client);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(res, Ok("Bonjour".to_string()));
+
+    let res = translator.detect("Hallo".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::German)));
+
+    let requests = transport.requests();
+    assert_eq!(requests.len(), 2);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(
+        requests[0].uri,
+        "https://translate.yandex.net/api/v1.5/tr.json/translate?key=key&lang=en-fr"
+    );
+    assert_eq!(requests[0].body_text(), "text=Hello");
+    assert_eq!(requests[1].method, "GET");
+    assert_eq!(
+        requests[1].uri,
+        "https://translate.yandex.net/api/v1.5/tr.json/detect?key=key&text=Hallo"
+    );
+}
+
+#[tokio::test]
+async fn memory_transport_errors() {
+    let client = HttpClient::with_transport(MemoryTransport::new(|_| {
+        Err(Error::RequestError("connection refused".to_string()))
+    }));
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::RequestError("connection refused".to_string()))
+    );
+}
+
+/// A transport counting the requests, then answering them in memory.
+struct Counting {
+    count: Arc<AtomicUsize>,
+    inner: MemoryTransport,
+}
+
+#[async_trait::async_trait]
+impl Transport for Counting {
+    async fn send(
+        &self,
+        request: http::Request<Vec<u8>>,
+    ) -> Result<http::Response<Vec<u8>>, Error> {
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.inner.send(request).await
+    }
+}
+
+#[tokio::test]
+async fn custom_transport() {
+    let count = Arc::new(AtomicUsize::new(0));
+    let client = HttpClient::with_transport(Counting {
+        count: count.clone(),
+        inner: MemoryTransport::with_response(
+            200,
+            r#"{"data":{"translations":[{"translatedText":"Bonjour"}]}}"#,
+        ),
+    });
+    let translator = GoogleV2::with_key("key").with_client(&client);
+
+    for _ in 0..2 {
+        let res = translator
+            .translate(
+                "Hello".to_string(),
+                InputLanguage::Automatic,
+                Language::French,
+            )
+            .await;
+        assert_eq!(res, Ok("Bonjour".to_string()));
+    }
+
+    assert_eq!(count.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,134 @@
+mod common;
+
+use common::{json, MockServer};
+use text_translator::*;
+
+#[tokio::test]
+async fn yandex_cloud_translate_with_iam_token() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"translations":[{"text":"Привет, мир!","detectedLanguageCode":"en"}]}"#,
+        )
+    })
+    .await;
+    let translator = YandexCloud::with_iam_token("t1.token")
+        .with_folder_id("b1gfolder")
+        .with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello, world!".to_string(),
+            InputLanguage::Automatic,
+            Language::Russian,
+        )
+        .await;
+    assert_eq!(res, Ok("Привет, мир!".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/translate");
+    assert_eq!(requests[0].header("authorization"), Some("Bearer t1.token"));
+    assert_eq!(
+        requests[0].body,
+        r#"{"folderId":"b1gfolder","texts":["Hello, world!"],"targetLanguageCode":"ru","format":"PLAIN_TEXT"}"#
+    );
+}
+
+#[tokio::test]
+async fn yandex_cloud_translate_texts_with_api_key() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"translations":[{"text":"Bonjour"},{"text":"Au revoir"}]}"#,
+        )
+    })
+    .await;
+    let translator = YandexCloud::with_key("AQVN-key").with_base_url(&server.url);
+
+    let res = translator
+        .translate_texts(
+            vec!["Hello".to_string(), "Goodbye".to_string()],
+            InputLanguage::Defined(Language::English),
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Ok(vec!["Bonjour".to_string(), "Au revoir".to_string()])
+    );
+
+    let requests = server.requests();
+    assert_eq!(
+        requests[0].header("authorization"),
+        Some("Api-Key AQVN-key")
+    );
+    assert_eq!(
+        requests[0].body,
+        r#"{"texts":["Hello","Goodbye"],"sourceLanguageCode":"en","targetLanguageCode":"fr","format":"PLAIN_TEXT"}"#
+    );
+}
+
+#[tokio::test]
+async fn yandex_cloud_detect_and_languages() {
+    let server = MockServer::start(|req| match req.uri.as_str() {
+        "/detect" => json(200, r#"{"languageCode":"de"}"#),
+        _ => json(
+            200,
+            r#"{"languages":[{"code":"de","name":"Deutsch"},{"code":"xx"},{"code":"ja","name":"日本語"}]}"#,
+        ),
+    })
+    .await;
+    let translator = YandexCloud::with_key("AQVN-key").with_base_url(&server.url);
+
+    let res = translator.detect("Guten Tag".to_string()).await;
+    assert_eq!(res, Ok(Some(Language::German)));
+
+    let res = translator.languages().await;
+    assert_eq!(res, Ok(vec![Language::German, Language::Japanese]));
+
+    let requests = server.requests();
+    assert_eq!(requests[0].body, r#"{"text":"Guten Tag"}"#);
+    assert_eq!(requests[1].uri, "/languages");
+    assert_eq!(requests[1].body, "{}");
+}
+
+#[tokio::test]
+async fn yandex_cloud_errors() {
+    let server = MockServer::start(|req| match req.header("authorization") {
+        Some("Bearer expired") => json(
+            401,
+            r#"{"code":16,"message":"The token is invalid","details":[]}"#,
+        ),
+        _ => json(429, "Too Many Requests"),
+    })
+    .await;
+
+    let res = YandexCloud::with_iam_token("expired")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::YandexCloudAPIError(
+            ProviderError::new(yandex_cloud::YandexCloudError::Unauthenticated, 401)
+                .with_code("16")
+                .with_message("The token is invalid")
+        ))
+    );
+
+    let res = YandexCloud::with_key("key")
+        .with_base_url(&server.url)
+        .detect("Hello".to_string())
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::YandexCloudAPIError(
+            yandex_cloud::YandexCloudError::ResourceExhausted.into()
+        ))
+    );
+
+    let res = YandexCloud::new().detect("Hello".to_string()).await;
+    assert_eq!(res, Err(Error::NoApiKeySet));
+}
@@ -0,0 +1,107 @@
+mod common;
+
+use common::{form_field, json, MockServer};
+use text_translator::{youdao::YoudaoError, *};
+
+#[test]
+fn youdao_sign() {
+    // sha256(appKey + input + salt + curtime + appSecret), as specified by the official documentation,
+    // computed with `printf 'app-keyhellosalt-11600000000secret' | sha256sum`
+    assert_eq!(
+        youdao::sign("app-key", "hello", "salt-1", "1600000000", "secret"),
+        "ab98048de68c6edf6eb07ba6dad644e38ba35d0bfab3c10fd2a1f6cf211b49b6"
+    );
+    // texts longer than 20 characters are truncated, counting characters and not bytes: the input is the
+    // first 10 characters, the length and the last 10 characters, "你好，我的名字是漩涡23很高兴认识你们大家！"
+    assert_eq!(
+        youdao::sign(
+            "app-key",
+            "你好，我的名字是漩涡鸣人。很高兴认识你们大家！",
+            "salt-1",
+            "1600000000",
+            "secret"
+        ),
+        "66279c7eb0964f86dc9c6cbcde5c272b67fa1462ca8c06f42d752acfc71db6e0"
+    );
+}
+
+#[tokio::test]
+async fn youdao_translate() {
+    let server = MockServer::start(|_| {
+        json(
+            200,
+            r#"{"errorCode":"0","query":"Hello","translation":["你好"],"l":"en2zh-CHS"}"#,
+        )
+    })
+    .await;
+    let translator = Youdao::with_credentials("app-key", "secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Defined(Language::English),
+            Language::Chinese,
+        )
+        .await;
+    assert_eq!(res, Ok("你好".to_string()));
+
+    let requests = server.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].uri, "/api");
+
+    let body = &requests[0].body;
+    assert_eq!(form_field(body, "q"), Some("Hello"));
+    assert_eq!(form_field(body, "from"), Some("en"));
+    assert_eq!(form_field(body, "to"), Some("zh-CHS"));
+    assert_eq!(form_field(body, "appKey"), Some("app-key"));
+    assert_eq!(form_field(body, "signType"), Some("v3"));
+    let salt = form_field(body, "salt").unwrap();
+    let curtime = form_field(body, "curtime").unwrap();
+    assert_eq!(
+        form_field(body, "sign"),
+        Some(youdao::sign("app-key", "Hello", salt, curtime, "secret").as_str())
+    );
+}
+
+#[tokio::test]
+async fn youdao_errors() {
+    let server = MockServer::start(|req| {
+        if req.body.contains("to=ja") {
+            json(200, r#"{"errorCode":"202"}"#)
+        } else {
+            json(200, r#"{"errorCode":"999"}"#)
+        }
+    })
+    .await;
+    let translator = Youdao::with_credentials("app-key", "secret").with_base_url(&server.url);
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::Japanese,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::YoudaoAPIError(
+            ProviderError::new(YoudaoError::InvalidSignature, 200).with_code("202")
+        ))
+    );
+
+    let res = translator
+        .translate(
+            "Hello".to_string(),
+            InputLanguage::Automatic,
+            Language::French,
+        )
+        .await;
+    assert_eq!(
+        res,
+        Err(Error::YoudaoAPIError(
+            ProviderError::new(YoudaoError::UnknownYoudaoCode, 200).with_code("999")
+        ))
+    );
+    assert_eq!(server.requests().len(), 2);
+}